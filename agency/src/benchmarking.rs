@@ -2,17 +2,20 @@
 
 use super::*;
 use crate::{Call as CollectiveCall, Config, Pallet as Collective};
-use dao::Call as DaoCall;
+use dao::{Call as DaoCall, Currency};
 use daos_doas::Call as DoAsCall;
 use frame_benchmarking::{
 	account, benchmarks, benchmarks_instance, benchmarks_instance_pallet,
 	impl_benchmark_test_suite, whitelisted_caller,
 };
-use frame_system::{Call as SystemCall, RawOrigin as SystemOrigin};
+use frame_system::{pallet_prelude::BlockNumberFor, Call as SystemCall, RawOrigin as SystemOrigin};
 use primitives::AccountIdConversion;
+use sp_runtime::traits::Bounded;
 
 fn get_alice<T: Config<I>, I: 'static>() -> T::AccountId {
-	account("alice", 1, 1)
+	let alice = account("alice", 1, 1);
+	<T as dao::Config>::Currency::make_free_balance_be(&alice, dao::BalanceOf::<T>::max_value());
+	alice
 }
 
 fn get_dao_account<T: Config<I>, I: 'static>(second_id: T::ConcreteId) -> T::AccountId {
@@ -26,11 +29,15 @@ fn create_dao<T: Config<I>, I: 'static>() -> (T::DaoId, T::ConcreteId) {
 		SystemOrigin::Signed(get_alice::<T, I>()).into(),
 		second_id,
 		vec![1; 4],
+		None,
 	)
 	.is_ok());
 	CollectiveMembers::<T, I>::insert(
 		dao_id,
-		vec![get_alice::<T, I>(), get_dao_account::<T, I>(second_id.clone())],
+		BoundedVec::truncate_from(vec![
+			get_alice::<T, I>(),
+			get_dao_account::<T, I>(second_id.clone()),
+		]),
 	);
 	(dao_id, second_id)
 }
@@ -101,7 +108,7 @@ benchmarks_instance_pallet! {
 	set_motion_duration {
 		let (dao_id, second_id) = create_dao::<T, I>();
 		let dao_account = get_dao_account::<T, I>(second_id);
-	}:_(SystemOrigin::Signed(dao_account), dao_id, T::BlockNumber::from(100u32))
+	}:_(SystemOrigin::Signed(dao_account), dao_id, BlockNumberFor::<T>::from(100u32))
 
 	set_max_proposals {
 		let (dao_id, second_id) = create_dao::<T, I>();
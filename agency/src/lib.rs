@@ -53,23 +53,28 @@
 //! 		.is_ok());
 //! ***
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	dispatch::{DispatchResultWithPostInfo, PostDispatchInfo, GetDispatchInfo},
 	ensure,
-	traits::{Get, StorageVersion},
+	storage::with_storage_layer,
+	traits::{Get, IsType, StorageVersion},
 	weights::{Weight},
+	BoundedVec,
 };
 pub use pallet::*;
 use primitives::{
-	traits::{EnsureOriginWithArg, SetCollectiveMembers},
+	traits::{AfterDissolve, ApplyTemplate, EnsureOriginWithArg, SetCollectiveMembers},
 	types::{DoAsEnsureOrigin, MemberCount, Proportion, ProposalIndex},
 };
 
 use frame_support::sp_runtime::traits::Hash;
 use frame_support::pallet_prelude::DispatchError;
 pub use scale_info::{prelude::boxed::Box, TypeInfo};
-use sp_runtime::{RuntimeDebug, traits::Dispatchable};
+use sp_runtime::{
+	traits::{Dispatchable, Saturating},
+	DispatchResult, RuntimeDebug,
+};
 use sp_std::{marker::PhantomData, prelude::*, result};
 use weights::WeightInfo;
 #[cfg(feature = "runtime-benchmarks")]
@@ -78,6 +83,7 @@ mod benchmarking;
 mod mock;
 #[cfg(test)]
 mod tests;
+pub mod migrations;
 pub mod traits;
 pub mod weights;
 
@@ -128,7 +134,8 @@ impl DefaultVote for MoreThanMajorityThenPrimeDefaultVote {
 }
 
 /// Origin for the collective module.
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[codec(mel_bound(DaoId: MaxEncodedLen))]
 #[scale_info(skip_type_params(I))]
 pub enum RawOrigin<DaoId, I> {
 	/// It has been condoned by a given number of members of the collective from a given total.
@@ -142,16 +149,18 @@ pub enum RawOrigin<DaoId, I> {
 }
 
 /// Info for keeping track of a motion being voted on.
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
-pub struct Votes<AccountId, BlockNumber> {
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[codec(mel_bound(AccountId: MaxEncodedLen, BlockNumber: MaxEncodedLen))]
+#[scale_info(skip_type_params(MaxMembers))]
+pub struct Votes<AccountId, BlockNumber, MaxMembers: Get<u32>> {
 	/// The proposal's unique index.
 	index: ProposalIndex,
 	/// The number of approval votes that are needed to pass the motion.
 	threshold: MemberCount,
 	/// The current set of voters that approved it.
-	ayes: Vec<AccountId>,
+	ayes: BoundedVec<AccountId, MaxMembers>,
 	/// The current set of voters that rejected it.
-	nays: Vec<AccountId>,
+	nays: BoundedVec<AccountId, MaxMembers>,
 	/// The hard end time of this vote.
 	end: BlockNumber,
 }
@@ -163,13 +172,18 @@ pub mod pallet {
 	use frame_system::pallet_prelude::*;
 	// use primitives::traits::BaseCallFilter;
 
+	pub type VotesOf<T, I = ()> = Votes<
+		<T as frame_system::Config>::AccountId,
+		BlockNumberFor<T>,
+		<T as Config<I>>::MaxMembersForSystem,
+	>;
+
 	/// The current storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(7);
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
 	#[pallet::storage_version(STORAGE_VERSION)]
-	#[pallet::without_storage_info]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
 	#[pallet::config]
@@ -205,6 +219,19 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxMembersForSystem: Get<MemberCount>;
 
+		/// Collective in DAO Maximum number of proposals at one time.
+		#[pallet::constant]
+		type MaxProposalsForSystem: Get<ProposalIndex>;
+
+		/// The maximum number of motions that are closed automatically in one block.
+		#[pallet::constant]
+		type MaxScheduledPerBlock: Get<u32>;
+
+		/// The maximum weight of the motions that are closed automatically in one block, with
+		/// their calls.
+		#[pallet::constant]
+		type MaxScheduledWeight: Get<Weight>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -216,8 +243,13 @@ pub mod pallet {
 	/// The hashes of the active proposals.
 	#[pallet::storage]
 	#[pallet::getter(fn proposals)]
-	pub type Proposals<T: Config<I>, I: 'static = ()> =
-		StorageMap<_, Identity, T::DaoId, Vec<T::Hash>, ValueQuery>;
+	pub type Proposals<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		T::DaoId,
+		BoundedVec<T::Hash, T::MaxProposalsForSystem>,
+		ValueQuery,
+	>;
 
 	/// The origin of each call.
 	#[pallet::storage]
@@ -235,8 +267,13 @@ pub mod pallet {
 	/// All members of the collective.
 	#[pallet::storage]
 	#[pallet::getter(fn collective_members)]
-	pub type CollectiveMembers<T: Config<I>, I: 'static = ()> =
-		StorageMap<_, Identity, <T as dao::Config>::DaoId, Vec<T::AccountId>, ValueQuery>;
+	pub type CollectiveMembers<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Identity,
+		<T as dao::Config>::DaoId,
+		BoundedVec<T::AccountId, T::MaxMembersForSystem>,
+		ValueQuery,
+	>;
 
 	/// The prime of the collective.
 	#[pallet::storage]
@@ -245,15 +282,21 @@ pub mod pallet {
 		StorageMap<_, Identity, <T as dao::Config>::DaoId, T::AccountId>;
 
 	#[pallet::type_value]
-	pub fn MotionDurationOnEmpty<T: Config<I>, I: 'static>() -> u32 {
-		u32::from(500u32)
+	pub fn MotionDurationOnEmpty<T: Config<I>, I: 'static>() -> BlockNumberFor<T> {
+		BlockNumberFor::<T>::from(500u32)
 	}
 
 	/// The time-out for council motions.
 	#[pallet::storage]
 	#[pallet::getter(fn motion_duration)]
-	pub type MotionDuration<T: Config<I>, I: 'static = ()> =
-		StorageMap<_, Identity, T::DaoId, u32, ValueQuery, MotionDurationOnEmpty<T, I>>;
+	pub type MotionDuration<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Identity,
+		T::DaoId,
+		BlockNumberFor<T>,
+		ValueQuery,
+		MotionDurationOnEmpty<T, I>,
+	>;
 
 	#[pallet::type_value]
 	pub fn MaxProposalsOnEmpty<T: Config<I>, I: 'static>() -> ProposalIndex {
@@ -278,13 +321,17 @@ pub mod pallet {
 		StorageMap<_, Identity, T::DaoId, MemberCount, ValueQuery, MaxMembersOnEmpty<T, I>>;
 
 	/// Actual proposal for a given hash, if it's current.
+	///
+	/// Left unbounded as in `pallet-collective`: each DAO has at most `MaxProposalsForSystem`
+	/// proposals and a proposal is never larger than the extrinsic that submitted it.
 	#[pallet::storage]
+	#[pallet::unbounded]
 	#[pallet::getter(fn proposal_of)]
 	pub type ProposalOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
 		_,
-		Identity,
+		Twox64Concat,
 		T::DaoId,
-		Identity,
+		Blake2_128Concat,
 		T::Hash,
 		<T as Config<I>>::Proposal,
 		OptionQuery,
@@ -295,11 +342,11 @@ pub mod pallet {
 	#[pallet::getter(fn voting)]
 	pub type Voting<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
 		_,
-		Identity,
+		Twox64Concat,
 		T::DaoId,
-		Identity,
+		Blake2_128Concat,
 		T::Hash,
-		Votes<T::AccountId, u32>,
+		VotesOf<T, I>,
 		OptionQuery,
 	>;
 
@@ -309,6 +356,47 @@ pub mod pallet {
 	pub type ProposalCount<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Identity, T::DaoId, u32, ValueQuery>;
 
+	/// The motions that are closed automatically at the beginning of each block.
+	#[pallet::storage]
+	#[pallet::getter(fn agenda)]
+	pub type Agenda<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<(T::DaoId, T::Hash, ProposalIndex), T::MaxScheduledPerBlock>,
+		ValueQuery,
+	>;
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
+		#[serde(skip)]
+		pub phantom: PhantomData<I>,
+		/// The collective members and the prime of each DAO.
+		pub members: Vec<(T::DaoId, Vec<T::AccountId>, Option<T::AccountId>)>,
+		/// The origin of each call of each DAO.
+		pub ensure_origins:
+			Vec<(T::DaoId, T::CallId, DoAsEnsureOrigin<Proportion<MemberCount>, MemberCount>)>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config<I>, I: 'static> BuildGenesisConfig for GenesisConfig<T, I> {
+		fn build(&self) {
+			for (dao_id, members, prime) in self.members.iter() {
+				<Pallet<T, I> as SetCollectiveMembers<_, _, _>>::set_members_sorted(
+					*dao_id,
+					members,
+					prime.clone(),
+				)
+				.expect("The members of the DAO are invalid.");
+			}
+			for (dao_id, call_id, ensure) in self.ensure_origins.iter() {
+				Pallet::<T, I>::ensure_proportion(ensure).expect("The proportion is invalid.");
+				EnsureOrigins::<T, I>::insert(dao_id, call_id, ensure);
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -340,7 +428,7 @@ pub mod pallet {
 		/// A proposal was closed because its threshold was reached or after its duration was up.
 		Closed { proposal_hash: T::Hash, yes: MemberCount, no: MemberCount },
 		/// Set the voting duration for a proposal in each DAO.
-		SetMotionDuration { dao_id: T::DaoId, duration: u32 },
+		SetMotionDuration { dao_id: T::DaoId, duration: BlockNumberFor<T> },
 		/// Set a cap on the number of proposals in each DAO.
 		SetMaxProposals { dao_id: T::DaoId, max: ProposalIndex },
 		/// Set the upper limit of the number of council members in each DAO.
@@ -384,10 +472,45 @@ pub mod pallet {
 		ThresholdTooLow,
 	}
 
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		/// Close the motions whose voting period ends at this block.
+		///
+		/// At most `MaxScheduledPerBlock` motions are closed in one block, and no more than
+		/// `MaxScheduledWeight`. A motion that is already closed is skipped, and one that doesn't
+		/// fit is moved to the next block, unless it is heavier than `MaxScheduledWeight` and is
+		/// left to the extrinsic.
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let max_weight = T::MaxScheduledWeight::get();
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			let next = now.saturating_add(1u32.into());
+			for (dao_id, proposal_hash, index) in Agenda::<T, I>::take(now) {
+				weight.saturating_accrue(T::DbWeight::get().reads(1));
+				// The proposal may be dispatched, so the weight is counted before closing it.
+				let proposal_weight = ProposalOf::<T, I>::get(dao_id, proposal_hash)
+					.map(|p| p.get_dispatch_info().weight)
+					.unwrap_or_default();
+				let task_weight =
+					<T as pallet::Config<I>>::WeightInfo::close().saturating_add(proposal_weight);
+				if weight.saturating_add(task_weight).any_gt(max_weight) {
+					if !task_weight.any_gt(max_weight) {
+						weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+						Self::schedule(next, dao_id, proposal_hash, index);
+					}
+					continue
+				}
+				weight.saturating_accrue(task_weight);
+				let _ = with_storage_layer(|| Self::do_close(dao_id, proposal_hash, index));
+			}
+			weight
+		}
+	}
+
 	// Note that councillor operations are assigned to the operational class.
 	#[pallet::call]
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		/// Dispatch a proposal from a member using the `Member` origin.
+		#[pallet::call_index(0)]
 		#[pallet::weight(<T as pallet::Config<I>>::WeightInfo::execute())]
 		pub fn execute(
 			origin: OriginFor<T>,
@@ -401,6 +524,7 @@ pub mod pallet {
 					dao::Error::<T>::InVailCall
 				);
 			}
+			Self::ensure_can_dispatch(dao_id, &proposal)?;
 			ensure!(Self::is_member(dao_id, &who)?, Error::<T, I>::NotMember);
 			let proposal_hash = T::Hashing::hash_of(&proposal);
 			let result = proposal.dispatch(RawOrigin::Member(dao_id).into());
@@ -413,6 +537,7 @@ pub mod pallet {
 		}
 
 		/// Add a new proposal to either be voted on or executed directly.
+		#[pallet::call_index(1)]
 		#[pallet::weight(<T as pallet::Config<I>>::WeightInfo::propose())]
 		pub fn propose(
 			origin: OriginFor<T>,
@@ -427,6 +552,7 @@ pub mod pallet {
 					dao::Error::<T>::InVailCall
 				);
 			}
+			Self::ensure_can_dispatch(dao_id, &proposal)?;
 			ensure!(threshold > 1 as MemberCount, Error::<T, I>::ThresholdTooLow);
 			ensure!(Self::is_member(dao_id, &who)?, Error::<T, I>::NotMember);
 			let proposal_hash = T::Hashing::hash_of(&proposal);
@@ -440,7 +566,7 @@ pub mod pallet {
 				Error::<T, I>::ThresholdWrong
 			);
 			<Proposals<T, I>>::try_mutate(dao_id, |proposals| -> DispatchResult {
-				proposals.push(proposal_hash);
+				proposals.try_push(proposal_hash).map_err(|_| Error::<T, I>::TooManyProposals)?;
 				ensure!(
 					proposals.len() as u32 <= MaxProposals::<T, I>::get(dao_id),
 					Error::<T, I>::WrongProposalLength
@@ -452,12 +578,15 @@ pub mod pallet {
 			<ProposalCount<T, I>>::mutate(dao_id, |i| *i += 1);
 			<ProposalOf<T, I>>::insert(dao_id, proposal_hash, *proposal);
 			let votes = {
-				let end =
-					frame_system::Pallet::<T>::block_number() + MotionDuration::<T, I>::get(dao_id).into();
-				// fixme
-				Votes { index, threshold, ayes: vec![who.clone()], nays: vec![], end: 100 }
+				let end = frame_system::Pallet::<T>::block_number()
+					.saturating_add(MotionDuration::<T, I>::get(dao_id));
+				let ayes = BoundedVec::try_from(vec![who.clone()])
+					.map_err(|_| Error::<T, I>::MembersTooLarge)?;
+				Votes { index, threshold, ayes, nays: BoundedVec::default(), end }
 			};
+			let end = votes.end;
 			<Voting<T, I>>::insert(dao_id, proposal_hash, votes);
+			Self::schedule(end, dao_id, proposal_hash, index);
 
 			Self::deposit_event(Event::Proposed {
 				account: who,
@@ -470,6 +599,7 @@ pub mod pallet {
 		}
 
 		/// Add an aye or nay vote for the sender to the given proposal.
+		#[pallet::call_index(2)]
 		#[pallet::weight(<T as pallet::Config<I>>::WeightInfo::vote())]
 		pub fn vote(
 			origin: OriginFor<T>,
@@ -490,7 +620,10 @@ pub mod pallet {
 
 			if approve {
 				if position_yes.is_none() {
-					voting.ayes.push(who.clone());
+					voting
+						.ayes
+						.try_push(who.clone())
+						.map_err(|_| Error::<T, I>::MembersTooLarge)?;
 				} else {
 					return Err(Error::<T, I>::DuplicateVote.into())
 				}
@@ -499,7 +632,10 @@ pub mod pallet {
 				}
 			} else {
 				if position_no.is_none() {
-					voting.nays.push(who.clone());
+					voting
+						.nays
+						.try_push(who.clone())
+						.map_err(|_| Error::<T, I>::MembersTooLarge)?;
 				} else {
 					return Err(Error::<T, I>::DuplicateVote.into())
 				}
@@ -524,6 +660,7 @@ pub mod pallet {
 		}
 
 		/// Close a vote that is either approved, disapproved or whose voting period has ended.
+		#[pallet::call_index(3)]
 		#[pallet::weight(<T as pallet::Config<I>>::WeightInfo::close())]
 		pub fn close(
 			origin: OriginFor<T>,
@@ -532,64 +669,13 @@ pub mod pallet {
 			#[pallet::compact] index: ProposalIndex,
 		) -> DispatchResultWithPostInfo {
 			let _ = ensure_signed(origin)?;
+			Self::do_close(dao_id, proposal_hash, index)?;
 
-			let voting =
-				Self::voting(dao_id, &proposal_hash).ok_or(Error::<T, I>::ProposalMissing)?;
-			ensure!(voting.index == index, Error::<T, I>::WrongIndex);
-
-			let mut no_votes = voting.nays.len() as MemberCount;
-			let mut yes_votes = voting.ayes.len() as MemberCount;
-			let seats = Self::collective_members(dao_id).len() as MemberCount;
-			let approved = yes_votes >= voting.threshold;
-			let disapproved = seats.saturating_sub(no_votes) < voting.threshold;
-			// Allow (dis-)approving the proposal as soon as there are enough votes.
-			if approved {
-				let proposal = Self::validate_and_get_proposal(&proposal_hash, dao_id)?;
-				Self::deposit_event(Event::Closed { proposal_hash, yes: yes_votes, no: no_votes });
-				let _ =
-					Self::do_approve_proposal(seats, yes_votes, proposal_hash, proposal, dao_id);
-				return Ok(().into())
-			} else if disapproved {
-				Self::deposit_event(Event::Closed { proposal_hash, yes: yes_votes, no: no_votes });
-				let _proposal_count = Self::do_disapprove_proposal(proposal_hash, dao_id);
-				return Ok(().into())
-			}
-
-			// Only allow actual closing of the proposal after the voting period has ended.
-			// fixme
-			// ensure!(
-			// 	frame_system::Pallet::<T>::block_number() >= voting.end,
-			// 	Error::<T, I>::TooEarly
-			// );
-
-			let prime_vote = Self::prime(dao_id).map(|who| voting.ayes.iter().any(|a| a == &who));
-
-			// default voting strategy.
-			let default = T::DefaultVote::default_vote(prime_vote, yes_votes, no_votes, seats);
-
-			let abstentions = seats - (yes_votes + no_votes);
-			match default {
-				true => yes_votes += abstentions,
-				false => no_votes += abstentions,
-			}
-			let approved = yes_votes >= voting.threshold;
-
-			if approved {
-				let proposal = Self::validate_and_get_proposal(&proposal_hash, dao_id)?;
-				Self::deposit_event(Event::Closed { proposal_hash, yes: yes_votes, no: no_votes });
-				let _ =
-					Self::do_approve_proposal(seats, yes_votes, proposal_hash, proposal, dao_id);
-				Ok(().into())
-			} else {
-				Self::deposit_event(Event::Closed { proposal_hash, yes: yes_votes, no: no_votes });
-				let _proposal_count = Self::do_disapprove_proposal(proposal_hash, dao_id);
-				Ok(().into())
-			}
+			Ok(().into())
 		}
 
-		/// call id:201
-		///
 		/// Disapprove a proposal, close, and remove it from the system, regardless of its current state.
+		#[pallet::call_index(4)]
 		#[pallet::weight(<T as pallet::Config<I>>::WeightInfo::disapprove_proposal())]
 		pub fn disapprove_proposal(
 			origin: OriginFor<T>,
@@ -601,14 +687,13 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		/// call id:202
-		///
 		/// Set the length of time for voting on proposal.
+		#[pallet::call_index(5)]
 		#[pallet::weight(<T as pallet::Config<I>>::WeightInfo::set_motion_duration())]
 		pub fn set_motion_duration(
 			origin: OriginFor<T>,
 			dao_id: T::DaoId,
-			duration: u32,
+			duration: BlockNumberFor<T>,
 		) -> DispatchResultWithPostInfo {
 			dao::Pallet::<T>::ensrue_dao_root(origin, dao_id)?;
 			MotionDuration::<T, I>::insert(dao_id, duration);
@@ -616,9 +701,8 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		/// call id:203
-		///
 		/// Set a cap on the number of agency proposals
+		#[pallet::call_index(6)]
 		#[pallet::weight(<T as pallet::Config<I>>::WeightInfo::set_max_proposals())]
 		pub fn set_max_proposals(
 			origin: OriginFor<T>,
@@ -631,9 +715,8 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		/// call id:204
-		///
 		/// Set the maximum number of members in the agency.
+		#[pallet::call_index(7)]
 		#[pallet::weight(<T as pallet::Config<I>>::WeightInfo::set_max_members())]
 		pub fn set_max_members(
 			origin: OriginFor<T>,
@@ -646,9 +729,8 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		/// call id:205
-		///
 		/// Set origin for a specific call.
+		#[pallet::call_index(8)]
 		#[pallet::weight(<T as pallet::Config<I>>::WeightInfo::set_ensure_origin_for_every_call())]
 		pub fn set_ensure_origin_for_every_call(
 			origin: OriginFor<T>,
@@ -657,17 +739,7 @@ pub mod pallet {
 			ensure: DoAsEnsureOrigin<Proportion<MemberCount>, MemberCount>,
 		) -> DispatchResultWithPostInfo {
 			dao::Pallet::<T>::ensrue_dao_root(origin, dao_id)?;
-
-			if let DoAsEnsureOrigin::Proportion(x) = ensure.clone() {
-				match x {
-					Proportion::MoreThan(n, m) => {
-						ensure!(n <= m, Error::<T, I>::ProportionErr);
-					},
-					Proportion::AtLeast(n, m) => {
-						ensure!(n <= m, Error::<T, I>::ProportionErr);
-					},
-				}
-			}
+			Self::ensure_proportion(&ensure)?;
 
 			EnsureOrigins::<T, I>::insert(dao_id, call_id, ensure.clone());
 			Self::deposit_event(Event::SetOrigin(dao_id, call_id, ensure));
@@ -708,6 +780,73 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		Ok(proposal)
 	}
 
+	/// A suspended DAO can only dispatch `resume_dao` for itself, and a dissolved DAO nothing.
+	fn ensure_can_dispatch(
+		dao_id: T::DaoId,
+		proposal: &<T as Config<I>>::Proposal,
+	) -> DispatchResult {
+		let call = <T as dao::Config>::Call::from_ref(proposal.into_ref());
+		dao::Pallet::<T>::ensure_can_dispatch(dao_id, call)
+	}
+
+	/// Close the motion if it is approved, disapproved or its voting period has ended, and
+	/// return the weight of the dispatched proposal.
+	fn do_close(
+		dao_id: T::DaoId,
+		proposal_hash: T::Hash,
+		index: ProposalIndex,
+	) -> Result<Weight, DispatchError> {
+		let voting = Self::voting(dao_id, &proposal_hash).ok_or(Error::<T, I>::ProposalMissing)?;
+		ensure!(voting.index == index, Error::<T, I>::WrongIndex);
+
+		let mut no_votes = voting.nays.len() as MemberCount;
+		let mut yes_votes = voting.ayes.len() as MemberCount;
+		let seats = Self::collective_members(dao_id).len() as MemberCount;
+		let approved = yes_votes >= voting.threshold;
+		let disapproved = seats.saturating_sub(no_votes) < voting.threshold;
+		// Allow (dis-)approving the proposal as soon as there are enough votes.
+		if approved {
+			let proposal = Self::validate_and_get_proposal(&proposal_hash, dao_id)?;
+			Self::ensure_can_dispatch(dao_id, &proposal)?;
+			Self::deposit_event(Event::Closed { proposal_hash, yes: yes_votes, no: no_votes });
+			let (proposal_weight, _) =
+				Self::do_approve_proposal(seats, yes_votes, proposal_hash, proposal, dao_id);
+			return Ok(proposal_weight)
+		} else if disapproved {
+			Self::deposit_event(Event::Closed { proposal_hash, yes: yes_votes, no: no_votes });
+			let _proposal_count = Self::do_disapprove_proposal(proposal_hash, dao_id);
+			return Ok(Weight::zero())
+		}
+
+		// Only allow actual closing of the proposal after the voting period has ended.
+		ensure!(frame_system::Pallet::<T>::block_number() >= voting.end, Error::<T, I>::TooEarly);
+
+		let prime_vote = Self::prime(dao_id).map(|who| voting.ayes.iter().any(|a| a == &who));
+
+		// default voting strategy.
+		let default = T::DefaultVote::default_vote(prime_vote, yes_votes, no_votes, seats);
+
+		let abstentions = seats - (yes_votes + no_votes);
+		match default {
+			true => yes_votes += abstentions,
+			false => no_votes += abstentions,
+		}
+		let approved = yes_votes >= voting.threshold;
+
+		if approved {
+			let proposal = Self::validate_and_get_proposal(&proposal_hash, dao_id)?;
+			Self::ensure_can_dispatch(dao_id, &proposal)?;
+			Self::deposit_event(Event::Closed { proposal_hash, yes: yes_votes, no: no_votes });
+			let (proposal_weight, _) =
+				Self::do_approve_proposal(seats, yes_votes, proposal_hash, proposal, dao_id);
+			Ok(proposal_weight)
+		} else {
+			Self::deposit_event(Event::Closed { proposal_hash, yes: yes_votes, no: no_votes });
+			let _proposal_count = Self::do_disapprove_proposal(proposal_hash, dao_id);
+			Ok(Weight::zero())
+		}
+	}
+
 	fn do_approve_proposal(
 		seats: MemberCount,
 		yes_votes: MemberCount,
@@ -749,6 +888,35 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		});
 		num_proposals as u32
 	}
+
+	/// Schedule the motion to be closed at `when`, or at the first later block whose agenda is
+	/// not full.
+	fn schedule(
+		mut when: BlockNumberFor<T>,
+		dao_id: T::DaoId,
+		proposal_hash: T::Hash,
+		index: ProposalIndex,
+	) {
+		while Agenda::<T, I>::try_append(when, (dao_id, proposal_hash, index)).is_err() {
+			when = when.saturating_add(1u32.into());
+		}
+	}
+
+	fn ensure_proportion(
+		ensure: &DoAsEnsureOrigin<Proportion<MemberCount>, MemberCount>,
+	) -> DispatchResult {
+		if let DoAsEnsureOrigin::Proportion(x) = ensure {
+			match x {
+				Proportion::MoreThan(n, m) => {
+					ensure!(n <= m, Error::<T, I>::ProportionErr);
+				},
+				Proportion::AtLeast(n, m) => {
+					ensure!(n <= m, Error::<T, I>::ProportionErr);
+				},
+			}
+		}
+		Ok(())
+	}
 }
 
 impl<T: Config<I>, I: 'static> SetCollectiveMembers<T::AccountId, T::DaoId, DispatchError>
@@ -765,21 +933,14 @@ impl<T: Config<I>, I: 'static> SetCollectiveMembers<T::AccountId, T::DaoId, Disp
 			return Err(Error::<T, I>::MembersTooLarge)?
 		}
 		// remove accounts from all current voting in motions.
-		let mut members = members.to_vec();
+		let mut members: BoundedVec<T::AccountId, T::MaxMembersForSystem> =
+			members.to_vec().try_into().map_err(|_| Error::<T, I>::MembersTooLarge)?;
 		members.sort();
 		for h in Self::proposals(dao_id).into_iter() {
 			<Voting<T, I>>::mutate(dao_id, h, |v| {
 				if let Some(mut votes) = v.take() {
-					votes.ayes = votes
-						.ayes
-						.into_iter()
-						.filter(|i| members.binary_search(i).is_ok())
-						.collect();
-					votes.nays = votes
-						.nays
-						.into_iter()
-						.filter(|i| members.binary_search(i).is_ok())
-						.collect();
+					votes.ayes.retain(|i| members.binary_search(i).is_ok());
+					votes.nays.retain(|i| members.binary_search(i).is_ok());
 					*v = Some(votes);
 				}
 			});
@@ -844,3 +1005,31 @@ impl<T: Config<I>, I: 'static>
 		<T as Config<I>>::Origin::from(RawOrigin::Root(a.0))
 	}
 }
+
+impl<T: Config<I>, I: 'static> AfterDissolve<T::DaoId> for Pallet<T, I> {
+	fn do_something(dao_id: T::DaoId) {
+		Proposals::<T, I>::remove(dao_id);
+		ProposalCount::<T, I>::remove(dao_id);
+		let _ = ProposalOf::<T, I>::clear_prefix(dao_id, u32::MAX, None);
+		let _ = Voting::<T, I>::clear_prefix(dao_id, u32::MAX, None);
+	}
+}
+
+impl<T: Config<I>, I: 'static> ApplyTemplate<T::AccountId, T::DaoId, dao::DaoTemplateOf<T>>
+	for Pallet<T, I>
+{
+	fn apply_template(
+		_creator: &T::AccountId,
+		dao_id: T::DaoId,
+		template: &dao::DaoTemplateOf<T>,
+	) -> DispatchResult {
+		if let Some(duration) = template.motion_duration {
+			MotionDuration::<T, I>::insert(dao_id, duration);
+		}
+		for (call_id, ensure) in template.ensure_origins.iter() {
+			Self::ensure_proportion(ensure)?;
+			EnsureOrigins::<T, I>::insert(dao_id, call_id, ensure.clone());
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,188 @@
+use super::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{DefensiveTruncateFrom, OnRuntimeUpgrade, StorageVersion},
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+
+/// Store the motion duration and the end of the motions as `BlockNumberFor<T>` instead of `u32`.
+pub mod v5 {
+	use super::*;
+
+	pub type OldVotesOf<T, I> =
+		Votes<<T as frame_system::Config>::AccountId, u32, <T as Config<I>>::MaxMembersForSystem>;
+
+	/// `Proposals` as it is keyed before the version 6.
+	#[frame_support::storage_alias]
+	pub type Proposals<T: Config<I>, I: 'static> = StorageMap<
+		Pallet<T, I>,
+		Identity,
+		<T as dao::Config>::DaoId,
+		Vec<<T as frame_system::Config>::Hash>,
+		ValueQuery,
+	>;
+
+	/// `ProposalOf` as it is keyed before the version 6.
+	#[frame_support::storage_alias]
+	pub type ProposalOf<T: Config<I>, I: 'static> = StorageDoubleMap<
+		Pallet<T, I>,
+		Identity,
+		<T as dao::Config>::DaoId,
+		Identity,
+		<T as frame_system::Config>::Hash,
+		<T as Config<I>>::Proposal,
+		OptionQuery,
+	>;
+
+	/// `Voting` as it is keyed before the version 6.
+	#[frame_support::storage_alias]
+	pub type Voting<T: Config<I>, I: 'static> = StorageDoubleMap<
+		Pallet<T, I>,
+		Identity,
+		<T as dao::Config>::DaoId,
+		Identity,
+		<T as frame_system::Config>::Hash,
+		VotesOf<T, I>,
+		OptionQuery,
+	>;
+
+	pub struct MigrateToV5<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV5<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T, I>>() != 4 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			MotionDuration::<T, I>::translate::<u32, _>(|_, old| {
+				translated += 1;
+				Some(BlockNumberFor::<T>::from(old))
+			});
+			// The votes are still keyed with `Identity` until the version 6.
+			v5::Voting::<T, I>::translate::<OldVotesOf<T, I>, _>(|_, _, old| {
+				translated += 1;
+				Some(Votes {
+					index: old.index,
+					threshold: old.threshold,
+					ayes: old.ayes,
+					nays: old.nays,
+					end: old.end.into(),
+				})
+			});
+			StorageVersion::new(5).put::<Pallet<T, I>>();
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			Ok((v5::Voting::<T, I>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let count = u32::decode(&mut &state[..]).map_err(|_| "The state is not a count.")?;
+			ensure!(
+				v5::Voting::<T, I>::iter().count() as u32 == count,
+				"Some votes are not migrated."
+			);
+			ensure!(StorageVersion::get::<Pallet<T, I>>() == 5, "The storage version is not 5.");
+			Ok(())
+		}
+	}
+}
+
+/// Key `Proposals`, `ProposalOf` and `Voting` with hashers that are safe for the proposal hashes.
+pub mod v6 {
+	use super::*;
+
+	pub struct MigrateToV6<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV6<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T, I>>() != 5 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let proposals = v5::Proposals::<T, I>::drain().collect::<Vec<_>>();
+			let calls = v5::ProposalOf::<T, I>::drain().collect::<Vec<_>>();
+			let votes = v5::Voting::<T, I>::drain().collect::<Vec<_>>();
+			let rekeyed = (proposals.len() + calls.len() + votes.len()) as u64;
+			for (dao_id, hashes) in proposals {
+				Proposals::<T, I>::insert(dao_id, BoundedVec::defensive_truncate_from(hashes));
+			}
+			for (dao_id, hash, proposal) in calls {
+				ProposalOf::<T, I>::insert(dao_id, hash, proposal);
+			}
+			for (dao_id, hash, vote) in votes {
+				Voting::<T, I>::insert(dao_id, hash, vote);
+			}
+			StorageVersion::new(6).put::<Pallet<T, I>>();
+			T::DbWeight::get().reads_writes(rekeyed + 1, rekeyed * 2 + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let daos = v5::Proposals::<T, I>::iter_keys().count() as u32;
+			let proposals = v5::ProposalOf::<T, I>::iter_keys().count() as u32;
+			let votes = v5::Voting::<T, I>::iter_keys().count() as u32;
+			Ok((daos, proposals, votes).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let (daos, proposals, votes) = <(u32, u32, u32)>::decode(&mut &state[..])
+				.map_err(|_| "The state is not the counts.")?;
+			ensure!(Proposals::<T, I>::iter().count() as u32 == daos, "Some hashes are lost.");
+			ensure!(
+				ProposalOf::<T, I>::iter().count() as u32 == proposals,
+				"Some proposals are lost."
+			);
+			ensure!(Voting::<T, I>::iter().count() as u32 == votes, "Some votes are lost.");
+			ensure!(StorageVersion::get::<Pallet<T, I>>() == 6, "The storage version is not 6.");
+			Ok(())
+		}
+	}
+}
+
+/// Schedule the motions that exist before they are closed automatically.
+pub mod v7 {
+	use super::*;
+
+	pub struct MigrateToV7<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV7<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T, I>>() != 6 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let next = frame_system::Pallet::<T>::block_number().saturating_add(1u32.into());
+			let mut scheduled = 0u64;
+			for (dao_id, hash, votes) in Voting::<T, I>::iter() {
+				scheduled += 1;
+				Pallet::<T, I>::schedule(votes.end.max(next), dao_id, hash, votes.index);
+			}
+			StorageVersion::new(7).put::<Pallet<T, I>>();
+			T::DbWeight::get().reads_writes(scheduled * 2 + 1, scheduled + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			Ok((Voting::<T, I>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let count = u32::decode(&mut &state[..]).map_err(|_| "The state is not a count.")?;
+			ensure!(
+				Agenda::<T, I>::iter_values().map(|tasks| tasks.len() as u32).sum::<u32>() >= count,
+				"Some motions are not scheduled."
+			);
+			ensure!(StorageVersion::get::<Pallet<T, I>>() == 7, "The storage version is not 7.");
+			Ok(())
+		}
+	}
+}
@@ -5,9 +5,15 @@ use frame_support::{
 	debug, parameter_types,
 	sp_tracing::debug,
 	traits::{ConstU16, ConstU32, ConstU64, Contains},
+	weights::Weight,
 };
 use frame_system;
-use primitives::{ids::Nft, traits::BaseCallFilter, types::MemberCount};
+use primitives::{
+	ids::Nft,
+	mock::Collections,
+	traits::BaseCallFilter,
+	types::{CallId, MemberCount},
+};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -27,6 +33,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		DAO: dao::{ Pallet, Call, Event<T>, Storage },
 		Agency: agency::{ Pallet, Call, Event<T>, Storage, Origin<T> },
 		Sudo: sudo::{ Pallet, Call, Event<T>, Storage },
@@ -61,14 +68,7 @@ impl frame_system::Config for Test {
 	type MaxConsumers = ConstU32<16>;
 }
 
-impl TryFrom<Call> for u64 {
-	type Error = ();
-	fn try_from(call: Call) -> Result<Self, Self::Error> {
-		match call {
-			_ => Ok(0u64),
-		}
-	}
-}
+primitives::impl_call_id!(Call);
 
 impl BaseCallFilter<Call> for Nft<u64> {
 	fn contains(&self, call: Call) -> bool {
@@ -87,18 +87,40 @@ impl BaseCallFilter<Call> for Nft<u64> {
 	}
 }
 
+impl pallet_balances::Config for Test {
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
 impl dao::Config for Test {
 	type Event = Event;
 	type Call = Call;
-	type CallId = u64;
+	type CallId = CallId;
 	type DaoId = u64;
 	type ConcreteId = Nft<u64>;
+	type Inspector = Collections;
 	type AfterCreate = ();
+	type AfterDissolve = (Agency, Sudo);
+	type AfterCreatorChanged = ();
+	type ApplyTemplate = (Agency, Sudo);
+	type MaxTemplateOrigins = ConstU32<10>;
+	type Currency = Balances;
+	type CreationDeposit = ConstU64<0>;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type StringLimit = ConstU32<50>;
 	type WeightInfo = ();
 }
 
 parameter_types! {
 	pub const MaxMembersForSystem: MemberCount = 4;
+	pub MaxScheduledWeight: Weight = Weight::from_all(1_000_000_000);
 }
 
 pub struct BaseCall;
@@ -116,6 +138,9 @@ impl agency::Config for Test {
 	type CollectiveBaseCallFilter = BaseCall;
 	type DefaultVote = agency::PrimeDefaultVote;
 	type MaxMembersForSystem = MaxMembersForSystem;
+	type MaxProposalsForSystem = ConstU32<100>;
+	type MaxScheduledPerBlock = ConstU32<10>;
+	type MaxScheduledWeight = MaxScheduledWeight;
 	type WeightInfo = ();
 }
 
@@ -131,6 +156,8 @@ impl daos_doas::Config for Test {
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	let t = GenesisConfig { system: Default::default() }.build_storage().unwrap();
+	let t = GenesisConfig { system: Default::default(), balances: Default::default() }
+		.build_storage()
+		.unwrap();
 	t.into()
 }
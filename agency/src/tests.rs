@@ -3,23 +3,33 @@
 
 use super::*;
 use crate::mock::{Call, Origin, *};
-use frame_support::{assert_noop, assert_ok, debug};
+use frame_support::{
+	assert_noop, assert_ok, debug,
+	storage::unhashed,
+	traits::{Hooks, OnRuntimeUpgrade, StorageVersion},
+};
 use primitives::{
 	ids::Nft,
-	types::Proportion::{AtLeast, MoreThan},
+	types::{
+		CallId,
+		Proportion::{AtLeast, MoreThan},
+	},
 };
-use sp_runtime::traits::BlakeTwo256;
+use sp_runtime::{traits::BlakeTwo256, BuildStorage};
 use sp_std::vec;
 use sudo;
 
 pub const ALICE: u64 = 1;
 
 pub fn create_dao() {
-	dao::Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 4]).unwrap();
+	dao::Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 4], None).unwrap();
 }
 
 pub fn set_members() {
-	crate::CollectiveMembers::<Test>::insert(0u64, vec![ALICE, 2u64, 3u64, 4u64])
+	crate::CollectiveMembers::<Test>::insert(
+		0u64,
+		BoundedVec::truncate_from(vec![ALICE, 2u64, 3u64, 4u64]),
+	)
 }
 
 pub fn set_sudo() {
@@ -35,11 +45,24 @@ pub fn set_sudo() {
 	assert_eq!(sudo::Account::<Test>::get(0u64), Some(1u64));
 }
 
+pub fn run_to_block(n: u64) {
+	while frame_system::Pallet::<Test>::block_number() < n {
+		let next = frame_system::Pallet::<Test>::block_number() + 1;
+		frame_system::Pallet::<Test>::set_block_number(next);
+		crate::Pallet::<Test>::on_initialize(next);
+	}
+}
+
+fn set_max_members_id() -> CallId {
+	CallId::from_call(&Call::Agency(crate::Call::set_max_members { dao_id: 0u64, max: 100u32 }))
+		.unwrap()
+}
+
 fn set_origin_for_0() {
 	set_sudo();
 	let proposal = Call::Agency(crate::Call::set_ensure_origin_for_every_call {
 		dao_id: 0u64,
-		call_id: 0u64,
+		call_id: set_max_members_id(),
 		ensure: DoAsEnsureOrigin::Member,
 	});
 	assert_ok!(sudo::Pallet::<Test>::sudo(
@@ -53,7 +76,7 @@ fn set_origin_for_0_1() {
 	set_sudo();
 	let proposal = Call::Agency(crate::Call::set_ensure_origin_for_every_call {
 		dao_id: 0u64,
-		call_id: 0u64,
+		call_id: set_max_members_id(),
 		ensure: DoAsEnsureOrigin::Members(2u32),
 	});
 	assert_ok!(sudo::Pallet::<Test>::sudo(
@@ -67,23 +90,23 @@ fn set_origin_for_p() {
 	set_sudo();
 	let proposal = Call::Agency(crate::Call::set_ensure_origin_for_every_call {
 		dao_id: 0u64,
-		call_id: 0u64,
+		call_id: set_max_members_id(),
 		ensure: DoAsEnsureOrigin::Proportion(MoreThan(5, 4)),
 	});
 	let proposal_1 = Call::Agency(crate::Call::set_ensure_origin_for_every_call {
 		dao_id: 0u64,
-		call_id: 0u64,
+		call_id: set_max_members_id(),
 		ensure: DoAsEnsureOrigin::Proportion(AtLeast(5, 4)),
 	});
 	assert_ok!(sudo::Pallet::<Test>::sudo(Origin::signed(ALICE), 0u64, Box::new(proposal)));
 	assert_ne!(
-		EnsureOrigins::<Test>::get(0u64, 0u64),
+		EnsureOrigins::<Test>::get(0u64, set_max_members_id()),
 		DoAsEnsureOrigin::Proportion(MoreThan(5, 4))
 	);
 
 	assert_ok!(sudo::Pallet::<Test>::sudo(Origin::signed(ALICE), 0u64, Box::new(proposal_1)));
 	assert_ne!(
-		EnsureOrigins::<Test>::get(0u64, 0u64),
+		EnsureOrigins::<Test>::get(0u64, set_max_members_id()),
 		DoAsEnsureOrigin::Proportion(MoreThan(5, 4))
 	);
 }
@@ -294,3 +317,265 @@ fn proposal_vote_close_should_work() {
 		));
 	});
 }
+
+#[test]
+fn expired_motion_should_be_closed_automatically() {
+	new_test_ext().execute_with(|| {
+		set_origin_for_0_1();
+		let set_max_members =
+			Call::Agency(crate::Call::set_max_members { dao_id: 0u64, max: 100u32 });
+		let do_as_agency = Call::DoAs(daos_doas::Call::do_as_agency {
+			dao_id: 0u64,
+			call: Box::new(set_max_members),
+		});
+		let hash = BlakeTwo256::hash_of(&do_as_agency);
+		assert_ok!(crate::Pallet::<Test>::propose(
+			Origin::signed(ALICE),
+			0u64,
+			2,
+			Box::new(do_as_agency.clone())
+		));
+		let end = MotionDuration::<Test>::get(0u64);
+		assert_eq!(Agenda::<Test>::get(end), vec![(0u64, hash, 0)]);
+		assert_ok!(crate::Pallet::<Test>::vote(Origin::signed(2u64), 0, hash, 0, true));
+
+		run_to_block(end - 1);
+		assert!(Voting::<Test>::contains_key(0u64, hash));
+		run_to_block(end);
+		assert!(!Voting::<Test>::contains_key(0u64, hash));
+		assert!(Agenda::<Test>::get(end).is_empty());
+		assert_eq!(crate::Pallet::<Test>::max_members(0u64), 100);
+
+		// A motion that is closed by the extrinsic is skipped.
+		MaxMembers::<Test>::insert(0u64, 50);
+		assert_ok!(crate::Pallet::<Test>::propose(
+			Origin::signed(ALICE),
+			0u64,
+			2,
+			Box::new(do_as_agency.clone())
+		));
+		assert_ok!(crate::Pallet::<Test>::vote(Origin::signed(2u64), 0, hash, 1, true));
+		assert_ok!(crate::Pallet::<Test>::close(Origin::signed(4u64), 0, hash, 1));
+		MaxMembers::<Test>::insert(0u64, 50);
+		run_to_block(2 * end);
+		assert_eq!(crate::Pallet::<Test>::max_members(0u64), 50);
+	});
+}
+
+#[test]
+fn motion_should_be_moved_when_the_agenda_is_full() {
+	new_test_ext().execute_with(|| {
+		set_origin_for_0_1();
+		let end = MotionDuration::<Test>::get(0u64);
+		let full = (0..10u32).map(|i| (1u64, Default::default(), i)).collect::<Vec<_>>();
+		Agenda::<Test>::insert(end, BoundedVec::truncate_from(full));
+		let proposal = Call::Agency(crate::Call::set_max_members { dao_id: 0u64, max: 100u32 });
+		let hash = BlakeTwo256::hash_of(&proposal);
+		assert_ok!(crate::Pallet::<Test>::propose(
+			Origin::signed(ALICE),
+			0u64,
+			2,
+			Box::new(proposal)
+		));
+		assert_eq!(Agenda::<Test>::get(end + 1), vec![(0u64, hash, 0)]);
+		run_to_block(end + 1);
+		assert!(!Voting::<Test>::contains_key(0u64, hash));
+	});
+}
+
+#[test]
+fn create_dao_from_template_should_work() {
+	new_test_ext().execute_with(|| {
+		let template = |ensure| dao::DaoTemplate {
+			launch_period: None,
+			voting_period: None,
+			reserve_period: None,
+			enactment_period: None,
+			minimum_deposit: None,
+			motion_duration: Some(100u64),
+			ensure_origins: vec![(set_max_members_id(), ensure)].try_into().unwrap(),
+			emergency_pledge: None,
+			sudo: false,
+		};
+		assert_ok!(dao::Pallet::<Test>::register_template(
+			Origin::root(),
+			template(DoAsEnsureOrigin::Proportion(MoreThan(5, 4)))
+		));
+		assert_ok!(dao::Pallet::<Test>::register_template(
+			Origin::root(),
+			template(DoAsEnsureOrigin::Proportion(AtLeast(1, 2)))
+		));
+		let create_dao_call = |template_id| {
+			Call::DAO(dao::Call::create_dao {
+				concrete_id: Nft(0u64),
+				describe: vec![1; 4],
+				template_id: Some(template_id),
+			})
+		};
+		assert_noop!(
+			create_dao_call(0u32).dispatch(Origin::signed(ALICE)),
+			Error::<Test>::ProportionErr
+		);
+		assert_ok!(create_dao_call(1u32).dispatch(Origin::signed(ALICE)));
+		assert_eq!(crate::MotionDuration::<Test>::get(0u64), 100u64);
+		assert_eq!(
+			EnsureOrigins::<Test>::get(0u64, set_max_members_id()),
+			DoAsEnsureOrigin::Proportion(AtLeast(1, 2))
+		);
+		assert_eq!(sudo::Account::<Test>::get(0u64), None);
+	});
+}
+
+#[test]
+fn genesis_config_should_work() {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	crate::GenesisConfig::<Test> {
+		phantom: Default::default(),
+		members: vec![(0u64, vec![3u64, ALICE, 2u64], Some(ALICE))],
+		ensure_origins: vec![(
+			0u64,
+			set_max_members_id(),
+			DoAsEnsureOrigin::Proportion(AtLeast(1, 2)),
+		)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		assert_eq!(crate::CollectiveMembers::<Test>::get(0u64), vec![ALICE, 2u64, 3u64]);
+		assert_eq!(crate::Prime::<Test>::get(0u64), Some(ALICE));
+		assert_eq!(
+			EnsureOrigins::<Test>::get(0u64, set_max_members_id()),
+			DoAsEnsureOrigin::Proportion(AtLeast(1, 2))
+		);
+	});
+}
+
+#[test]
+fn migrate_to_v5_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(4).put::<Pallet<Test>>();
+		let hash = BlakeTwo256::hash_of(&0u64);
+		unhashed::put(&MotionDuration::<Test>::hashed_key_for(0u64), &100u32);
+		let old: migrations::v5::OldVotesOf<Test, ()> = Votes {
+			index: 0,
+			threshold: 2,
+			ayes: BoundedVec::truncate_from(vec![ALICE]),
+			nays: BoundedVec::truncate_from(vec![2u64]),
+			end: 200u32,
+		};
+		unhashed::put(&migrations::v5::Voting::<Test, ()>::hashed_key_for(0u64, hash), &old);
+		migrations::v5::MigrateToV5::<Test>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<Pallet<Test>>(), 5);
+		assert_eq!(MotionDuration::<Test>::get(0u64), 100u64);
+		let votes = migrations::v5::Voting::<Test, ()>::get(0u64, hash).unwrap();
+		assert_eq!(votes.end, 200u64);
+		assert_eq!(votes.ayes, vec![ALICE]);
+		assert_eq!(votes.nays, vec![2u64]);
+	});
+}
+
+#[test]
+fn migrate_to_v6_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(5).put::<Pallet<Test>>();
+		let proposal = Call::Agency(crate::Call::set_max_members { dao_id: 0u64, max: 100u32 });
+		let hash = BlakeTwo256::hash_of(&proposal);
+		migrations::v5::Proposals::<Test, ()>::insert(0u64, vec![hash]);
+		migrations::v5::ProposalOf::<Test, ()>::insert(0u64, hash, proposal.clone());
+		migrations::v5::Voting::<Test, ()>::insert(
+			0u64,
+			hash,
+			Votes {
+				index: 0,
+				threshold: 2,
+				ayes: BoundedVec::truncate_from(vec![ALICE]),
+				nays: BoundedVec::default(),
+				end: 200u64,
+			},
+		);
+		assert!(Voting::<Test>::get(0u64, hash).is_none());
+
+		migrations::v6::MigrateToV6::<Test>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<Pallet<Test>>(), 6);
+		assert_eq!(Proposals::<Test>::get(0u64), vec![hash]);
+		assert_eq!(ProposalOf::<Test>::get(0u64, hash), Some(proposal));
+		assert_eq!(Voting::<Test>::get(0u64, hash).unwrap().ayes, vec![ALICE]);
+		assert!(!migrations::v5::Voting::<Test, ()>::contains_key(0u64, hash));
+	});
+}
+
+#[test]
+fn migrate_to_v7_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(6).put::<Pallet<Test>>();
+		frame_system::Pallet::<Test>::set_block_number(300);
+		let proposal = Call::Agency(crate::Call::set_max_members { dao_id: 0u64, max: 100u32 });
+		let hash = BlakeTwo256::hash_of(&proposal);
+		let votes = |index: ProposalIndex, end: u64| Votes {
+			index,
+			threshold: 2,
+			ayes: BoundedVec::truncate_from(vec![ALICE]),
+			nays: BoundedVec::default(),
+			end,
+		};
+		Voting::<Test>::insert(0u64, hash, votes(0, 200u64));
+		Voting::<Test>::insert(1u64, hash, votes(1, 400u64));
+
+		migrations::v7::MigrateToV7::<Test>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<Pallet<Test>>(), 7);
+		// A motion that has already ended is closed in the next block.
+		assert_eq!(Agenda::<Test>::get(301u64), vec![(0u64, hash, 0)]);
+		assert_eq!(Agenda::<Test>::get(400u64), vec![(1u64, hash, 1)]);
+	});
+}
+
+#[test]
+fn suspended_dao_should_not_dispatch() {
+	new_test_ext().execute_with(|| {
+		set_origin_for_0_1();
+		let set_max_members =
+			Call::Agency(crate::Call::set_max_members { dao_id: 0u64, max: 100u32 });
+		assert_ok!(crate::Pallet::<Test>::propose(
+			Origin::signed(ALICE),
+			0u64,
+			2,
+			Box::new(set_max_members.clone())
+		));
+		let hash = BlakeTwo256::hash_of(&set_max_members);
+		let dao_account = dao::Daos::<Test>::get(0u64).unwrap().dao_account_id;
+		assert_ok!(dao::Pallet::<Test>::suspend_dao(Origin::signed(dao_account), 0u64));
+
+		assert_noop!(
+			crate::Pallet::<Test>::execute(
+				Origin::signed(ALICE),
+				0u64,
+				Box::new(set_max_members.clone())
+			),
+			dao::Error::<Test>::DaoNotActive
+		);
+		assert_noop!(
+			crate::Pallet::<Test>::propose(
+				Origin::signed(ALICE),
+				0u64,
+				2,
+				Box::new(Call::Agency(crate::Call::set_max_members { dao_id: 0u64, max: 10u32 }))
+			),
+			dao::Error::<Test>::DaoNotActive
+		);
+		assert_ok!(crate::Pallet::<Test>::vote(Origin::signed(2u64), 0u64, hash, 0, true));
+		assert_noop!(
+			crate::Pallet::<Test>::close(Origin::signed(ALICE), 0u64, hash, 0),
+			dao::Error::<Test>::DaoNotActive
+		);
+		// Only `resume_dao` of the DAO itself can be dispatched.
+		assert_ok!(crate::Pallet::<Test>::execute(
+			Origin::signed(ALICE),
+			0u64,
+			Box::new(Call::DAO(dao::Call::resume_dao { dao_id: 0u64 }))
+		));
+
+		assert_ok!(dao::Pallet::<Test>::dissolve_dao(Origin::signed(dao_account), 0u64));
+		assert_eq!(ProposalCount::<Test>::get(0u64), 0);
+		assert!(ProposalOf::<Test>::get(0u64, hash).is_none());
+	});
+}
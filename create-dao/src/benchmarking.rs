@@ -4,22 +4,56 @@ use crate::{Config, Pallet as Dao};
 use frame_benchmarking::{
 	account, benchmarks, benchmarks_instance, impl_benchmark_test_suite, whitelisted_caller,
 };
+use frame_support::traits::Get;
 use frame_system::RawOrigin as SystemOrigin;
 use primitives::AccountIdConversion;
+use sp_runtime::traits::{Bounded, One, TrailingZeroInput};
 
 fn get_alice<T: Config>() -> T::AccountId {
-	account("alice", 1, 1)
+	let alice = account("alice", 1, 1);
+	T::Currency::make_free_balance_be(&alice, BalanceOf::<T>::max_value());
+	alice
+}
+
+fn get_bob<T: Config>() -> T::AccountId {
+	let bob = account("bob", 2, 2);
+	T::Currency::make_free_balance_be(&bob, BalanceOf::<T>::max_value());
+	bob
 }
 
 fn get_dao_account<T: Config>(second_id: T::ConcreteId) -> T::AccountId {
 	second_id.into_account()
 }
 
+fn get_template<T: Config>() -> DaoTemplateOf<T> {
+	DaoTemplate {
+		launch_period: Some(100u32.into()),
+		voting_period: Some(100u32.into()),
+		reserve_period: Some(100u32.into()),
+		enactment_period: Some(100u32.into()),
+		minimum_deposit: Some(BalanceOf::<T>::max_value()),
+		motion_duration: Some(100u32.into()),
+		ensure_origins: Default::default(),
+		emergency_pledge: Some(BalanceOf::<T>::max_value()),
+		sudo: true,
+	}
+}
+
 fn creat_dao<T: Config>() -> (T::DaoId, T::ConcreteId) {
 	let alice = get_alice::<T>();
 	let dao_id = T::DaoId::default();
 	let second_id: T::ConcreteId = Default::default();
-	assert!(Dao::<T>::create_dao(SystemOrigin::Signed(alice).into(), second_id, vec![1; 4]).is_ok());
+	assert!(Dao::<T>::create_dao(SystemOrigin::Signed(alice).into(), second_id, vec![1; 4], None)
+		.is_ok());
+	(dao_id, second_id)
+}
+
+fn creat_child_dao<T: Config>() -> (T::DaoId, T::ConcreteId) {
+	let alice = get_alice::<T>();
+	let dao_id = T::DaoId::default() + One::one();
+	let second_id = T::ConcreteId::decode(&mut TrailingZeroInput::new(&[1u8][..])).unwrap();
+	assert!(Dao::<T>::create_dao(SystemOrigin::Signed(alice).into(), second_id, vec![1; 4], None)
+		.is_ok());
 	(dao_id, second_id)
 }
 
@@ -38,4 +72,147 @@ benchmarks! {
 		let dao_account = get_dao_account::<T>(second_id);
 		let remark = vec![1; 50];
 	}:_(SystemOrigin::Signed(dao_account), dao_id, remark)
+
+	suspend_dao {
+		let (dao_id, second_id) = creat_dao::<T>();
+		let dao_account = get_dao_account::<T>(second_id);
+	}:_(SystemOrigin::Signed(dao_account), dao_id)
+	verify {
+		assert_eq!(Dao::<T>::try_get_status(dao_id), Ok(Status::InActive));
+	}
+
+	resume_dao {
+		let (dao_id, second_id) = creat_dao::<T>();
+		let dao_account = get_dao_account::<T>(second_id);
+		assert!(Dao::<T>::suspend_dao(SystemOrigin::Signed(dao_account.clone()).into(), dao_id).is_ok());
+	}:_(SystemOrigin::Signed(dao_account), dao_id)
+	verify {
+		assert_eq!(Dao::<T>::try_get_status(dao_id), Ok(Status::Active));
+	}
+
+	dissolve_dao {
+		let (dao_id, second_id) = creat_dao::<T>();
+		let dao_account = get_dao_account::<T>(second_id);
+	}:_(SystemOrigin::Signed(dao_account), dao_id)
+	verify {
+		assert_eq!(Dao::<T>::try_get_status(dao_id), Ok(Status::Dissolved));
+	}
+
+	slash_dao {
+		let (dao_id, _) = creat_dao::<T>();
+		let origin = T::ForceOrigin::try_successful_origin().map_err(|_| "no force origin")?;
+	}:_<T::RuntimeOrigin>(origin, dao_id)
+	verify {
+		assert_eq!(Dao::<T>::try_get_status(dao_id), Ok(Status::Dissolved));
+	}
+
+	set_metadata {
+		let (dao_id, second_id) = creat_dao::<T>();
+		let dao_account = get_dao_account::<T>(second_id);
+		let limit = T::StringLimit::get() as usize;
+	}:_(SystemOrigin::Signed(dao_account), dao_id, vec![1; limit], vec![1; limit], vec![1; limit], Some(T::Hash::default()))
+	verify {
+		assert_eq!(Dao::<T>::metadata_of(dao_id).name.len(), limit);
+	}
+
+	set_sub_account {
+		let (dao_id, second_id) = creat_dao::<T>();
+		let dao_account = get_dao_account::<T>(second_id);
+	}:_(SystemOrigin::Signed(dao_account), dao_id, 1u32, true)
+	verify {
+		assert!(Dao::<T>::sub_accounts(dao_id, 1u32).is_some());
+	}
+
+	dispatch_as_sub_account {
+		let (dao_id, second_id) = creat_dao::<T>();
+		let dao_account = get_dao_account::<T>(second_id);
+		assert!(Dao::<T>::set_sub_account(
+			SystemOrigin::Signed(dao_account.clone()).into(),
+			dao_id,
+			1u32,
+			true
+		)
+		.is_ok());
+		let call: <T as Config>::Call = frame_system::Call::<T>::remark { remark: vec![1; 50] }.into();
+	}:_(SystemOrigin::Signed(dao_account), dao_id, 1u32, Box::new(call))
+
+	register_template {
+		let origin = T::ForceOrigin::try_successful_origin().map_err(|_| "no force origin")?;
+	}:_<T::RuntimeOrigin>(origin, get_template::<T>())
+	verify {
+		assert!(Dao::<T>::templates(0u32).is_some());
+	}
+
+	remove_template {
+		let origin = T::ForceOrigin::try_successful_origin().map_err(|_| "no force origin")?;
+		assert!(Dao::<T>::register_template(origin.clone(), get_template::<T>()).is_ok());
+	}:_<T::RuntimeOrigin>(origin, 0u32)
+	verify {
+		assert!(Dao::<T>::templates(0u32).is_none());
+	}
+
+	set_parent {
+		let (parent, _) = creat_dao::<T>();
+		let (dao_id, second_id) = creat_child_dao::<T>();
+		let dao_account = get_dao_account::<T>(second_id);
+	}:_(SystemOrigin::Signed(dao_account), dao_id, Some(parent))
+	verify {
+		assert_eq!(Dao::<T>::pending_parent(dao_id), Some(parent));
+	}
+
+	set_call_list_mode {
+		let (dao_id, second_id) = creat_dao::<T>();
+		let dao_account = get_dao_account::<T>(second_id);
+	}:_(SystemOrigin::Signed(dao_account), dao_id, CallListMode::Allow)
+	verify {
+		assert_eq!(Dao::<T>::call_list_mode(dao_id), CallListMode::Allow);
+	}
+
+	set_call_listed {
+		let (dao_id, second_id) = creat_dao::<T>();
+		let dao_account = get_dao_account::<T>(second_id);
+	}:_(SystemOrigin::Signed(dao_account), dao_id, T::CallId::default(), true)
+	verify {
+		assert!(Dao::<T>::call_list(dao_id, T::CallId::default()).is_some());
+	}
+
+	transfer_creator {
+		let (dao_id, second_id) = creat_dao::<T>();
+		let dao_account = get_dao_account::<T>(second_id);
+		let bob = get_bob::<T>();
+	}:_(SystemOrigin::Signed(dao_account), dao_id, Some(bob.clone()))
+	verify {
+		assert_eq!(Dao::<T>::pending_creator(dao_id), Some(bob));
+	}
+
+	accept_creator {
+		let (dao_id, second_id) = creat_dao::<T>();
+		let dao_account = get_dao_account::<T>(second_id);
+		let bob = get_bob::<T>();
+		assert!(Dao::<T>::transfer_creator(
+			SystemOrigin::Signed(dao_account).into(),
+			dao_id,
+			Some(bob.clone())
+		)
+		.is_ok());
+	}:_(SystemOrigin::Signed(bob.clone()), dao_id)
+	verify {
+		assert_eq!(Dao::<T>::try_get_creator(dao_id), Ok(bob));
+	}
+
+	accept_parent {
+		let (parent, parent_second_id) = creat_dao::<T>();
+		let parent_account = get_dao_account::<T>(parent_second_id);
+		let (dao_id, second_id) = creat_child_dao::<T>();
+		let dao_account = get_dao_account::<T>(second_id);
+		assert!(Dao::<T>::set_parent(
+			SystemOrigin::Signed(dao_account).into(),
+			dao_id,
+			Some(parent)
+		)
+		.is_ok());
+	}:_(SystemOrigin::Signed(parent_account), dao_id)
+	verify {
+		assert_eq!(Dao::<T>::children_of(parent), vec![dao_id]);
+	}
 }
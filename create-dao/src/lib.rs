@@ -34,17 +34,22 @@
 pub use codec::{MaxEncodedLen, Decode, Encode};
 pub use frame_support::{
 	// codec::{Decode, Encode},
-	traits::IsSubType,
+	traits::{Currency, IsSubType, ReservableCurrency},
+	BoundedVec,
 };
-use frame_support::{sp_runtime::traits::BlockNumberProvider, };
 pub use pallet::*;
 pub use primitives::{
-	traits::{AfterCreate, BaseCallFilter, TryCreate},
-	types::RealCallId,
+	traits::{
+		AfterCreate, AfterCreatorChanged, AfterDissolve, ApplyTemplate, BaseCallFilter, TryCreate,
+	},
+	types::{DoAsEnsureOrigin, MemberCount, Proportion, RealCallId},
 	AccountIdConversion,
 };
 pub use scale_info::{prelude::boxed::Box, TypeInfo};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 // use sp_runtime::traits::BlockNumberProvider;
+use frame_system::pallet_prelude::BlockNumberFor;
 pub use sp_runtime::{traits::Hash, RuntimeDebug};
 pub use sp_std::{
 	marker::PhantomData,
@@ -60,19 +65,37 @@ pub mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod migrations;
 pub mod weights;
 /// DAO's status.
 #[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Status {
 	/// In use.
 	Active,
-	/// Does not work properly.
+	/// Suspended, only `resume_dao` can be dispatched until it is resumed.
 	InActive,
+	/// Dissolved permanently.
+	Dissolved,
+}
+
+/// How the DAO treats the calls in its call list.
+#[derive(
+	PartialEq, Eq, Clone, Copy, Default, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum CallListMode {
+	/// The listed calls can not be dispatched.
+	#[default]
+	Deny,
+	/// Only the listed calls can be dispatched.
+	Allow,
 }
 
 /// DAO specific information
 #[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
-pub struct DaoInfo<AccountId, BlockNumber, ConcreteId, Status> {
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DaoInfo<AccountId, BlockNumber, ConcreteId, Status, Balance, DaoId> {
 	/// creator of DAO.
 	creator: AccountId,
 	/// The block that creates the DAO.
@@ -81,21 +104,78 @@ pub struct DaoInfo<AccountId, BlockNumber, ConcreteId, Status> {
 	pub concrete_id: ConcreteId,
 	/// DAO account id.
 	pub dao_account_id: AccountId,
-	/// Description of the DAO.
-	// describe: Vec<u8>,
 	/// State of the DAO.
 	status: Status,
+	/// Amount reserved from the creator.
+	pub deposit: Balance,
+	/// The DAO whose DAO account is also the root of this DAO.
+	pub parent: Option<DaoId>,
+}
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Metadata of the DAO.
+#[derive(PartialEq, Eq, Clone, Default, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct DaoMetadata<BoundedString, Hash> {
+	/// Name of the DAO.
+	pub name: BoundedString,
+	/// Description of the DAO.
+	pub description: BoundedString,
+	/// Website of the DAO.
+	pub website: BoundedString,
+	/// Hash of the DAO's logo.
+	pub logo: Option<Hash>,
 }
 
+pub type DaoMetadataOf<T> =
+	DaoMetadata<BoundedVec<u8, <T as Config>::StringLimit>, <T as frame_system::Config>::Hash>;
+
+/// Default governance parameters of the DAOs created from a template.
+///
+/// `None` keeps the default value of the module.
+#[derive(PartialEq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct DaoTemplate<BlockNumber, Balance, EnsureOrigins> {
+	/// How soon can a referendum be called in `square`.
+	pub launch_period: Option<BlockNumber>,
+	/// How long the referendum lasts in `square`.
+	pub voting_period: Option<BlockNumber>,
+	/// Deprecated, the deposits of the seconds are returned when the proposal is launched in
+	/// `square`. Kept so that the templates still decode.
+	pub reserve_period: Option<BlockNumber>,
+	/// How long before the approved proposal is enacted in `square`.
+	pub enactment_period: Option<BlockNumber>,
+	/// Minimum stake when making public proposals in `square`.
+	pub minimum_deposit: Option<Balance>,
+	/// How long the motion lasts in `agency`.
+	pub motion_duration: Option<BlockNumber>,
+	/// The origin that `agency` requires for each call.
+	pub ensure_origins: EnsureOrigins,
+	/// The pledge of the internal proposals in `emergency`.
+	pub emergency_pledge: Option<Balance>,
+	/// Whether the creator becomes the sudo account.
+	pub sudo: bool,
+}
+
+pub type DaoTemplateOf<T> = DaoTemplate<
+	BlockNumberFor<T>,
+	BalanceOf<T>,
+	BoundedVec<
+		(<T as Config>::CallId, DoAsEnsureOrigin<Proportion<MemberCount>, MemberCount>),
+		<T as Config>::MaxTemplateOrigins,
+	>,
+>;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 	use frame_support::{
-		dispatch::DispatchResultWithPostInfo, pallet_prelude::*, traits::UnfilteredDispatchable,
-		// weights::GetDispatchInfo,
+		dispatch::{DispatchResultWithPostInfo, GetDispatchInfo},
+		pallet_prelude::*,
+		traits::UnfilteredDispatchable,
 	};
 	use frame_system::pallet_prelude::*;
-	use sp_runtime::traits::{CheckedAdd, One};
+	use sp_runtime::traits::{CheckedAdd, One, Zero};
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
@@ -106,13 +186,16 @@ pub mod pallet {
 		/// All calls supported by DAO.
 		type Call: Parameter
 			+ UnfilteredDispatchable<RuntimeOrigin = Self::RuntimeOrigin>
-			// + GetDispatchInfo
+			+ GetDispatchInfo
 			+ From<frame_system::Call<Self>>
 			+ From<Call<Self>>
 			+ IsSubType<Call<Self>>
 			+ IsType<<Self as frame_system::Config>::RuntimeCall>;
 
 		/// Each Call has its own id.
+		///
+		/// `primitives::types::CallId` with `impl_call_id!` derives it from the pallet index and
+		/// the call index.
 		type CallId: Parameter
 			+ Copy
 			+ MaybeSerializeDeserialize
@@ -122,11 +205,20 @@ pub mod pallet {
 			+ TryFrom<<Self as pallet::Config>::Call>;
 
 		/// Each DAO has its own id.
-		type DaoId: Clone + Default + Copy + Parameter + Member + MaxEncodedLen + CheckedAdd + One;
+		type DaoId: Clone
+			+ Default
+			+ Copy
+			+ Parameter
+			+ Member
+			+ MaybeSerializeDeserialize
+			+ MaxEncodedLen
+			+ CheckedAdd
+			+ One;
 
 		/// The specific group on the chain mapped by DAO.
 		type ConcreteId: Parameter
 			+ Member
+			+ MaybeSerializeDeserialize
 			+ TypeInfo
 			+ MaxEncodedLen
 			+ Clone
@@ -134,17 +226,51 @@ pub mod pallet {
 			+ Default
 			+ AccountIdConversion<Self::AccountId>
 			+ BaseCallFilter<<Self as pallet::Config>::Call>
-			+ TryCreate<Self::AccountId, Self::DaoId, DispatchError>;
+			+ TryCreate<Self::AccountId, Self::DaoId, DispatchError, Self::Inspector>;
+
+		/// Who owns the specific group, such as the nft or assets module.
+		type Inspector;
 
 		/// Do some things after creating dao, such as setting up a sudo account.
 		type AfterCreate: AfterCreate<Self::AccountId, Self::DaoId>;
 
+		/// Clean up what other modules keep for a DAO after it is dissolved.
+		type AfterDissolve: AfterDissolve<Self::DaoId>;
+
+		/// Do some things after the creator of the DAO is changed.
+		type AfterCreatorChanged: AfterCreatorChanged<Self::AccountId, Self::DaoId>;
+
+		/// Set the default governance parameters of the template in other modules.
+		type ApplyTemplate: ApplyTemplate<Self::AccountId, Self::DaoId, DaoTemplateOf<Self>>;
+
+		/// The maximum number of call origins in a template.
+		#[pallet::constant]
+		type MaxTemplateOrigins: Get<u32>;
+
+		/// The currency used to reserve the creation deposit.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The amount reserved from the creator when creating a DAO.
+		#[pallet::constant]
+		type CreationDeposit: Get<BalanceOf<Self>>;
+
+		/// The origin that can slash the deposit of a spam DAO.
+		type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum length of the DAO's name, description and website.
+		#[pallet::constant]
+		type StringLimit: Get<u32>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	// #[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -155,19 +281,153 @@ pub mod pallet {
 		_,
 		Identity,
 		T::DaoId,
-		DaoInfo<T::AccountId, u32, T::ConcreteId, Status>,
+		DaoInfo<T::AccountId, BlockNumberFor<T>, T::ConcreteId, Status, BalanceOf<T>, T::DaoId>,
 	>;
 
+	/// The DAO that each specific group is mapped to.
+	///
+	/// The entry is kept after the DAO is dissolved, since the account of a DAO is derived from
+	/// its group, so a group can only ever create one DAO.
+	#[pallet::storage]
+	#[pallet::getter(fn dao_id_of)]
+	pub type DaoIdOf<T: Config> = StorageMap<_, Identity, T::ConcreteId, T::DaoId>;
+
+	/// Metadata of the DAO.
+	#[pallet::storage]
+	#[pallet::getter(fn metadata_of)]
+	pub type MetadataOf<T: Config> =
+		StorageMap<_, Identity, T::DaoId, DaoMetadataOf<T>, ValueQuery>;
+
+	/// Sub-accounts that the DAO allows its governance to dispatch as.
+	#[pallet::storage]
+	#[pallet::getter(fn sub_accounts)]
+	pub type SubAccounts<T: Config> =
+		StorageDoubleMap<_, Identity, T::DaoId, Identity, u32, (), OptionQuery>;
+
+	/// The child DAOs of each DAO.
+	#[pallet::storage]
+	pub type Children<T: Config> =
+		StorageDoubleMap<_, Identity, T::DaoId, Identity, T::DaoId, (), OptionQuery>;
+
+	/// The DAOs suspended by their parent, only the parent can resume them.
+	#[pallet::storage]
+	#[pallet::getter(fn suspended_by_parent)]
+	pub type SuspendedByParent<T: Config> = StorageMap<_, Identity, T::DaoId, ()>;
+
+	/// The account that the creator role of the DAO is being transferred to.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_creator)]
+	pub type PendingCreator<T: Config> = StorageMap<_, Identity, T::DaoId, T::AccountId>;
+
+	/// The DAO that is asked to become the parent of the DAO.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_parent)]
+	pub type PendingParent<T: Config> = StorageMap<_, Identity, T::DaoId, T::DaoId>;
+
+	/// Whether the call list of the DAO is an allowlist or a denylist.
+	#[pallet::storage]
+	#[pallet::getter(fn call_list_mode)]
+	pub type CallListModeOf<T: Config> =
+		StorageMap<_, Identity, T::DaoId, CallListMode, ValueQuery>;
+
+	/// The calls listed by the DAO, on top of the calls allowed by `BaseCallFilter`.
+	#[pallet::storage]
+	#[pallet::getter(fn call_list)]
+	pub type CallList<T: Config> =
+		StorageDoubleMap<_, Identity, T::DaoId, Identity, T::CallId, (), OptionQuery>;
+
+	/// All registered DAO templates.
+	#[pallet::storage]
+	#[pallet::getter(fn templates)]
+	pub type Templates<T: Config> = StorageMap<_, Identity, u32, DaoTemplateOf<T>>;
+
+	/// The id of the next template to be registered.
+	#[pallet::storage]
+	#[pallet::getter(fn next_template_id)]
+	pub type NextTemplateId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
 	/// The id of the next dao to be created.
 	#[pallet::storage]
 	#[pallet::getter(fn next_dao_id)]
 	pub type NextDaoId<T: Config> = StorageValue<_, T::DaoId, ValueQuery>;
 
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// The DAOs that exist from the genesis block, with their creator and description.
+		///
+		/// No deposit is reserved for them.
+		pub daos: Vec<(T::AccountId, T::ConcreteId, Vec<u8>)>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			for (creator, concrete_id, describe) in self.daos.iter() {
+				assert!(
+					!DaoIdOf::<T>::contains_key(concrete_id),
+					"The specific group already has a DAO."
+				);
+				let description: BoundedVec<u8, T::StringLimit> =
+					describe.clone().try_into().expect("The description is too long.");
+				let dao_id = NextDaoId::<T>::get();
+				Daos::<T>::insert(
+					dao_id,
+					DaoInfo {
+						creator: creator.clone(),
+						start_block: Zero::zero(),
+						concrete_id: *concrete_id,
+						status: Status::Active,
+						dao_account_id: concrete_id.into_account(),
+						deposit: Zero::zero(),
+						parent: None,
+					},
+				);
+				DaoIdOf::<T>::insert(concrete_id, dao_id);
+				MetadataOf::<T>::insert(dao_id, DaoMetadata { description, ..Default::default() });
+				let next_id = dao_id.checked_add(&One::one()).expect("The dao id overflows.");
+				NextDaoId::<T>::put(next_id);
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// The new DAO is successfully created.
 		CreatedDao(T::AccountId, T::DaoId, T::ConcreteId),
+		/// The DAO is suspended.
+		SuspendedDao(T::DaoId),
+		/// The DAO is resumed.
+		ResumedDao(T::DaoId),
+		/// The DAO is dissolved.
+		DissolvedDao(T::DaoId),
+		/// The deposit of the spam DAO is slashed.
+		SlashedDao(T::DaoId, BalanceOf<T>),
+		/// Set the metadata of the DAO.
+		SetMetadata(T::DaoId, DaoMetadataOf<T>),
+		/// Allow or disallow the sub-account of the DAO.
+		SetSubAccount(T::DaoId, u32, bool),
+		/// The DAO executes a call as its sub-account.
+		SubAccountDispatched(T::DaoId, u32, DispatchResult),
+		/// The new template is registered.
+		RegisteredTemplate(u32),
+		/// The template is removed.
+		RemovedTemplate(u32),
+		/// The template is applied to the new DAO.
+		AppliedTemplate(T::DaoId, u32),
+		/// Set the parent of the DAO.
+		SetParent(T::DaoId, Option<T::DaoId>),
+		/// Set the DAO that is asked to become the parent of the DAO.
+		SetPendingParent(T::DaoId, Option<T::DaoId>),
+		/// Set the mode of the call list of the DAO.
+		SetCallListMode(T::DaoId, CallListMode),
+		/// Add the call to or remove it from the call list of the DAO.
+		SetCallListed(T::DaoId, T::CallId, bool),
+		/// Set the account that the creator role of the DAO is being transferred to.
+		SetPendingCreator(T::DaoId, Option<T::AccountId>),
+		/// The creator of the DAO is changed from the first account to the second one.
+		ChangedCreator(T::DaoId, T::AccountId, T::AccountId),
 	}
 
 	#[pallet::error]
@@ -186,52 +446,107 @@ pub mod pallet {
 		DaoIdNotMatch,
 		/// The description of the DAO is too long.
 		DescribeTooLong,
+		/// The name of the DAO is too long.
+		NameTooLong,
+		/// The website of the DAO is too long.
+		WebsiteTooLong,
+		/// The sub-account is not allowed by the DAO.
+		SubAccountNotAllowed,
+		/// Template does not exist.
+		TemplateNotExists,
+		/// The DAO can not be a descendant of itself.
+		ParentCycle,
+		/// The DAO is suspended by its parent.
+		SuspendedByParent,
+		/// The call is not allowed by the call list of the DAO.
+		CallNotAllowed,
+		/// The account is not the pending creator of the DAO.
+		NotPendingCreator,
+		/// The account is not the DAO account of the pending parent of the DAO.
+		NotPendingParent,
 		/// Numerical calculation overflow error.
 		Overflow,
+		/// The DAO is not active.
+		DaoNotActive,
+		/// The DAO is not suspended.
+		DaoNotSuspended,
+		/// The DAO has been dissolved.
+		DaoDissolved,
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Create a DAO for a specific group
+		///
+		/// The governance parameters of the template are applied to the new DAO.
+		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::create_dao())]
 		pub fn create_dao(
 			origin: OriginFor<T>,
 			concrete_id: T::ConcreteId,
 			describe: Vec<u8>,
+			template_id: Option<u32>,
 		) -> DispatchResultWithPostInfo {
 			let creator = ensure_signed(origin)?;
 
-			ensure!(describe.len() <= 50, Error::<T>::DescribeTooLong);
+			let description: BoundedVec<u8, T::StringLimit> =
+				describe.try_into().map_err(|_| Error::<T>::DescribeTooLong)?;
+			ensure!(!DaoIdOf::<T>::contains_key(concrete_id), Error::<T>::DaoExists);
+			let template = match template_id {
+				Some(id) => Some(Templates::<T>::get(id).ok_or(Error::<T>::TemplateNotExists)?),
+				None => None,
+			};
 			let dao_id = NextDaoId::<T>::get();
 
-			if !cfg!(any(feature = "std", feature = "runtime-benchmarks")) {
-				concrete_id.try_create(creator.clone(), dao_id)?;
+			if !cfg!(feature = "runtime-benchmarks") {
+				TryCreate::<T::AccountId, T::DaoId, DispatchError, T::Inspector>::try_create(
+					&concrete_id,
+					creator.clone(),
+					dao_id,
+				)
+				.map_err(|e| {
+					frame_support::log::debug!(
+						"{:?} can not create a DAO for {:?}: {:?}",
+						creator,
+						concrete_id,
+						e
+					);
+					Error::<T>::HaveNoCreatePermission
+				})?;
 			}
 
-			// fixme
-			let now = frame_system::Pallet::<T>::current_block_number();
+			let deposit = T::CreationDeposit::get();
+			T::Currency::reserve(&creator, deposit)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
 
 			Daos::<T>::insert(
 				dao_id,
 				DaoInfo {
 					creator: creator.clone(),
-					start_block: 100,
+					start_block: now,
 					concrete_id,
-					// describe,
 					status: Status::Active,
 					dao_account_id: concrete_id.into_account(),
+					deposit,
+					parent: None,
 				},
 			);
+			DaoIdOf::<T>::insert(concrete_id, dao_id);
+			MetadataOf::<T>::insert(dao_id, DaoMetadata { description, ..Default::default() });
 			let next_id = dao_id.checked_add(&One::one()).ok_or(Error::<T>::Overflow)?;
 			NextDaoId::<T>::put(next_id);
 			T::AfterCreate::do_something(creator.clone(), dao_id);
-			Self::deposit_event(Event::CreatedDao(creator, dao_id, concrete_id));
+			Self::deposit_event(Event::CreatedDao(creator.clone(), dao_id, concrete_id));
+			if let (Some(id), Some(template)) = (template_id, template) {
+				T::ApplyTemplate::apply_template(&creator, dao_id, &template)?;
+				Self::deposit_event(Event::AppliedTemplate(dao_id, id));
+			}
 			Ok(().into())
 		}
 
-		/// call id:101
-		///
 		/// dao remark something.
+		#[pallet::call_index(1)]
 		#[pallet::weight(T::WeightInfo::dao_remark())]
 		pub fn dao_remark(
 			origin: OriginFor<T>,
@@ -241,9 +556,360 @@ pub mod pallet {
 			Self::ensrue_dao_root(origin, dao_id)?;
 			Ok(().into())
 		}
+
+		/// Suspend the DAO, only `resume_dao` can be dispatched until it is resumed.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::suspend_dao())]
+		pub fn suspend_dao(origin: OriginFor<T>, dao_id: T::DaoId) -> DispatchResultWithPostInfo {
+			let who = Self::ensrue_dao_root(origin, dao_id)?;
+			Daos::<T>::try_mutate(dao_id, |dao| -> DispatchResult {
+				let dao = dao.as_mut().ok_or(Error::<T>::DaoNotExists)?;
+				ensure!(dao.status == Status::Active, Error::<T>::DaoNotActive);
+				dao.status = Status::InActive;
+				Ok(())
+			})?;
+			if Self::is_parent_account(dao_id, &who) {
+				SuspendedByParent::<T>::insert(dao_id, ());
+			}
+			Self::deposit_event(Event::SuspendedDao(dao_id));
+			Ok(().into())
+		}
+
+		/// Resume the suspended DAO.
+		///
+		/// The DAO suspended by its parent can only be resumed by the parent.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::resume_dao())]
+		pub fn resume_dao(origin: OriginFor<T>, dao_id: T::DaoId) -> DispatchResultWithPostInfo {
+			let who = Self::ensrue_dao_root(origin, dao_id)?;
+			if SuspendedByParent::<T>::contains_key(dao_id) {
+				ensure!(Self::is_parent_account(dao_id, &who), Error::<T>::SuspendedByParent);
+				SuspendedByParent::<T>::remove(dao_id);
+			}
+			Daos::<T>::try_mutate(dao_id, |dao| -> DispatchResult {
+				let dao = dao.as_mut().ok_or(Error::<T>::DaoNotExists)?;
+				ensure!(dao.status == Status::InActive, Error::<T>::DaoNotSuspended);
+				dao.status = Status::Active;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::ResumedDao(dao_id));
+			Ok(().into())
+		}
+
+		/// Dissolve the DAO permanently.
+		///
+		/// Proposals, votes and reserves kept by other modules are cleaned up and the creation
+		/// deposit is returned. The specific group stays mapped to the dissolved DAO, so it can
+		/// only ever create one DAO.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::dissolve_dao())]
+		pub fn dissolve_dao(origin: OriginFor<T>, dao_id: T::DaoId) -> DispatchResultWithPostInfo {
+			Self::ensrue_dao_root(origin, dao_id)?;
+			Self::do_dissolve(dao_id, false)?;
+			Ok(().into())
+		}
+
+		/// Slash the creation deposit of a spam DAO and dissolve it.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::slash_dao())]
+		pub fn slash_dao(origin: OriginFor<T>, dao_id: T::DaoId) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+			Self::do_dissolve(dao_id, true)?;
+			Ok(().into())
+		}
+
+		/// Set the name, description, website and logo of the DAO.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::set_metadata())]
+		pub fn set_metadata(
+			origin: OriginFor<T>,
+			dao_id: T::DaoId,
+			name: Vec<u8>,
+			description: Vec<u8>,
+			website: Vec<u8>,
+			logo: Option<T::Hash>,
+		) -> DispatchResultWithPostInfo {
+			Self::ensrue_dao_root(origin, dao_id)?;
+			let metadata = DaoMetadata {
+				name: name.try_into().map_err(|_| Error::<T>::NameTooLong)?,
+				description: description.try_into().map_err(|_| Error::<T>::DescribeTooLong)?,
+				website: website.try_into().map_err(|_| Error::<T>::WebsiteTooLong)?,
+				logo,
+			};
+			MetadataOf::<T>::insert(dao_id, metadata.clone());
+			Self::deposit_event(Event::SetMetadata(dao_id, metadata));
+			Ok(().into())
+		}
+
+		/// Allow or disallow the DAO's governance to dispatch as the `index`th sub-account.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::set_sub_account())]
+		pub fn set_sub_account(
+			origin: OriginFor<T>,
+			dao_id: T::DaoId,
+			index: u32,
+			allow: bool,
+		) -> DispatchResultWithPostInfo {
+			Self::ensrue_dao_root(origin, dao_id)?;
+			if allow {
+				SubAccounts::<T>::insert(dao_id, index, ());
+			} else {
+				SubAccounts::<T>::remove(dao_id, index);
+			}
+			Self::deposit_event(Event::SetSubAccount(dao_id, index, allow));
+			Ok(().into())
+		}
+
+		/// Execute a call as the `index`th sub-account of the DAO.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::dispatch_as_sub_account())]
+		pub fn dispatch_as_sub_account(
+			origin: OriginFor<T>,
+			dao_id: T::DaoId,
+			index: u32,
+			call: Box<<T as pallet::Config>::Call>,
+		) -> DispatchResultWithPostInfo {
+			Self::ensrue_dao_root(origin, dao_id)?;
+			let sub_account = Self::try_get_sub_account(dao_id, index)?;
+			ensure!(
+				Self::try_get_concrete_id(dao_id)?.contains(*call.clone()),
+				Error::<T>::InVailCall
+			);
+			Self::ensure_call_allowed(dao_id, &call)?;
+			Self::ensure_can_dispatch(dao_id, &call)?;
+			let res =
+				call.dispatch_bypass_filter(frame_system::RawOrigin::Signed(sub_account).into());
+			Self::deposit_event(Event::SubAccountDispatched(
+				dao_id,
+				index,
+				res.map(|_| ()).map_err(|e| e.error),
+			));
+			Ok(().into())
+		}
+
+		/// Set the parent of the DAO, the DAO account of the parent is also the root of this DAO.
+		///
+		/// The parent has to accept it. `None` detaches the DAO from its parent and cancels the
+		/// pending one.
+		///
+		/// The DAO that already has a parent can only be moved or detached by the parent.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::set_parent())]
+		pub fn set_parent(
+			origin: OriginFor<T>,
+			dao_id: T::DaoId,
+			parent: Option<T::DaoId>,
+		) -> DispatchResultWithPostInfo {
+			let who = Self::ensrue_dao_root(origin, dao_id)?;
+			let dao = Self::try_get_dao(dao_id)?;
+			ensure!(dao.status != Status::Dissolved, Error::<T>::DaoDissolved);
+			if dao.parent.is_some() {
+				ensure!(Self::is_parent_account(dao_id, &who), Error::<T>::BadOrigin);
+			}
+			match parent {
+				Some(parent) => {
+					Self::ensure_can_be_parent(dao_id, parent)?;
+					PendingParent::<T>::insert(dao_id, parent);
+				},
+				None => {
+					PendingParent::<T>::remove(dao_id);
+					if dao.parent.is_some() {
+						Self::do_set_parent(dao_id, None)?;
+					}
+				},
+			}
+			Self::deposit_event(Event::SetPendingParent(dao_id, parent));
+			Ok(().into())
+		}
+
+		/// Register a DAO template.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::register_template())]
+		pub fn register_template(
+			origin: OriginFor<T>,
+			template: DaoTemplateOf<T>,
+		) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let template_id = NextTemplateId::<T>::get();
+			Templates::<T>::insert(template_id, template);
+			NextTemplateId::<T>::put(template_id.checked_add(1).ok_or(Error::<T>::Overflow)?);
+			Self::deposit_event(Event::RegisteredTemplate(template_id));
+			Ok(().into())
+		}
+
+		/// Remove a DAO template, the DAOs created from it are not affected.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::remove_template())]
+		pub fn remove_template(
+			origin: OriginFor<T>,
+			template_id: u32,
+		) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+			Templates::<T>::take(template_id).ok_or(Error::<T>::TemplateNotExists)?;
+			Self::deposit_event(Event::RemovedTemplate(template_id));
+			Ok(().into())
+		}
+
+		/// Set whether the call list of the DAO is an allowlist or a denylist.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::set_call_list_mode())]
+		pub fn set_call_list_mode(
+			origin: OriginFor<T>,
+			dao_id: T::DaoId,
+			mode: CallListMode,
+		) -> DispatchResultWithPostInfo {
+			Self::ensrue_dao_root(origin, dao_id)?;
+			CallListModeOf::<T>::insert(dao_id, mode);
+			Self::deposit_event(Event::SetCallListMode(dao_id, mode));
+			Ok(().into())
+		}
+
+		/// Add the call to or remove it from the call list of the DAO.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::set_call_listed())]
+		pub fn set_call_listed(
+			origin: OriginFor<T>,
+			dao_id: T::DaoId,
+			call_id: T::CallId,
+			listed: bool,
+		) -> DispatchResultWithPostInfo {
+			Self::ensrue_dao_root(origin, dao_id)?;
+			if listed {
+				CallList::<T>::insert(dao_id, call_id, ());
+			} else {
+				CallList::<T>::remove(dao_id, call_id);
+			}
+			Self::deposit_event(Event::SetCallListed(dao_id, call_id, listed));
+			Ok(().into())
+		}
+
+		/// Transfer the creator role of the DAO, the new creator has to accept it.
+		///
+		/// `None` cancels the pending transfer.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::transfer_creator())]
+		pub fn transfer_creator(
+			origin: OriginFor<T>,
+			dao_id: T::DaoId,
+			new_creator: Option<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			Self::ensrue_dao_root(origin, dao_id)?;
+			ensure!(Self::try_get_status(dao_id)? != Status::Dissolved, Error::<T>::DaoDissolved);
+			match new_creator.clone() {
+				Some(who) => PendingCreator::<T>::insert(dao_id, who),
+				None => PendingCreator::<T>::remove(dao_id),
+			}
+			Self::deposit_event(Event::SetPendingCreator(dao_id, new_creator));
+			Ok(().into())
+		}
+
+		/// Accept the creator role of the DAO.
+		///
+		/// The creation deposit is reserved from the new creator and returned to the old one.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::accept_creator())]
+		pub fn accept_creator(
+			origin: OriginFor<T>,
+			dao_id: T::DaoId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				PendingCreator::<T>::get(dao_id).as_ref() == Some(&who),
+				Error::<T>::NotPendingCreator
+			);
+			let old = Daos::<T>::try_mutate(dao_id, |dao| -> Result<_, DispatchError> {
+				let dao = dao.as_mut().ok_or(Error::<T>::DaoNotExists)?;
+				ensure!(dao.status != Status::Dissolved, Error::<T>::DaoDissolved);
+				T::Currency::reserve(&who, dao.deposit)?;
+				T::Currency::unreserve(&dao.creator, dao.deposit);
+				Ok(sp_std::mem::replace(&mut dao.creator, who.clone()))
+			})?;
+			PendingCreator::<T>::remove(dao_id);
+			T::AfterCreatorChanged::do_something(dao_id, &old, &who);
+			Self::deposit_event(Event::ChangedCreator(dao_id, old, who));
+			Ok(().into())
+		}
+
+		/// Accept the DAO as a child, called by the DAO account of its pending parent.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::accept_parent())]
+		pub fn accept_parent(origin: OriginFor<T>, dao_id: T::DaoId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let parent = PendingParent::<T>::get(dao_id).ok_or(Error::<T>::NotPendingParent)?;
+			ensure!(Self::try_get_dao_account_id(parent)? == who, Error::<T>::NotPendingParent);
+			ensure!(Self::try_get_status(dao_id)? != Status::Dissolved, Error::<T>::DaoDissolved);
+			Self::ensure_can_be_parent(dao_id, parent)?;
+			PendingParent::<T>::remove(dao_id);
+			Self::do_set_parent(dao_id, Some(parent))?;
+			Ok(().into())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// The parent must not be dissolved or a descendant of the DAO.
+		fn ensure_can_be_parent(dao_id: T::DaoId, parent: T::DaoId) -> DispatchResult {
+			ensure!(Self::try_get_status(parent)? != Status::Dissolved, Error::<T>::DaoDissolved);
+			let mut ancestor = Some(parent);
+			while let Some(id) = ancestor {
+				ensure!(id != dao_id, Error::<T>::ParentCycle);
+				ancestor = Self::try_get_dao(id)?.parent;
+			}
+			Ok(())
+		}
+
+		fn do_set_parent(dao_id: T::DaoId, parent: Option<T::DaoId>) -> DispatchResult {
+			let old = Daos::<T>::try_mutate(dao_id, |dao| -> Result<_, DispatchError> {
+				let dao = dao.as_mut().ok_or(Error::<T>::DaoNotExists)?;
+				Ok(sp_std::mem::replace(&mut dao.parent, parent))
+			})?;
+			if let Some(old) = old {
+				Children::<T>::remove(old, dao_id);
+				SuspendedByParent::<T>::remove(dao_id);
+			}
+			if let Some(parent) = parent {
+				Children::<T>::insert(parent, dao_id, ());
+			}
+			Self::deposit_event(Event::SetParent(dao_id, parent));
+			Ok(())
+		}
+
+		fn do_dissolve(dao_id: T::DaoId, slash: bool) -> DispatchResult {
+			let deposit = Daos::<T>::try_mutate(dao_id, |dao| -> Result<_, DispatchError> {
+				let dao = dao.as_mut().ok_or(Error::<T>::DaoNotExists)?;
+				ensure!(dao.status != Status::Dissolved, Error::<T>::DaoDissolved);
+				dao.status = Status::Dissolved;
+				let _ = SubAccounts::<T>::clear_prefix(dao_id, u32::MAX, None);
+				let _ = CallList::<T>::clear_prefix(dao_id, u32::MAX, None);
+				CallListModeOf::<T>::remove(dao_id);
+				PendingCreator::<T>::remove(dao_id);
+				PendingParent::<T>::remove(dao_id);
+				if slash {
+					let _ = T::Currency::slash_reserved(&dao.creator, dao.deposit);
+				} else {
+					T::Currency::unreserve(&dao.creator, dao.deposit);
+				}
+				Ok(sp_std::mem::take(&mut dao.deposit))
+			})?;
+			if let Some(parent) = Self::try_get_dao(dao_id)?.parent {
+				Children::<T>::remove(parent, dao_id);
+			}
+			SuspendedByParent::<T>::remove(dao_id);
+			for child in Children::<T>::drain_prefix(dao_id).map(|(child, _)| child) {
+				Daos::<T>::mutate(child, |dao| {
+					if let Some(dao) = dao.as_mut() {
+						dao.parent = None;
+					}
+				});
+				SuspendedByParent::<T>::remove(child);
+			}
+			T::AfterDissolve::do_something(dao_id);
+			if slash {
+				Self::deposit_event(Event::SlashedDao(dao_id, deposit));
+			}
+			Self::deposit_event(Event::DissolvedDao(dao_id));
+			Ok(())
+		}
+
 		pub fn try_get_creator(
 			dao_id: <T as pallet::Config>::DaoId,
 		) -> result::Result<T::AccountId, DispatchError> {
@@ -253,7 +919,10 @@ pub mod pallet {
 
 		pub fn try_get_dao(
 			dao_id: <T as pallet::Config>::DaoId,
-		) -> Result<DaoInfo<T::AccountId, u32, T::ConcreteId, Status>, DispatchError> {
+		) -> Result<
+			DaoInfo<T::AccountId, BlockNumberFor<T>, T::ConcreteId, Status, BalanceOf<T>, T::DaoId>,
+			DispatchError,
+		> {
 			let dao = Daos::<T>::get(dao_id).ok_or(Error::<T>::DaoNotExists)?;
 			Ok(dao)
 		}
@@ -265,6 +934,13 @@ pub mod pallet {
 			Ok(dao.concrete_id)
 		}
 
+		pub fn try_get_dao_id(
+			concrete_id: T::ConcreteId,
+		) -> result::Result<<T as pallet::Config>::DaoId, DispatchError> {
+			let dao_id = DaoIdOf::<T>::get(concrete_id).ok_or(Error::<T>::DaoNotExists)?;
+			Ok(dao_id)
+		}
+
 		pub fn try_get_dao_account_id(
 			dao_id: <T as pallet::Config>::DaoId,
 		) -> result::Result<T::AccountId, DispatchError> {
@@ -272,13 +948,117 @@ pub mod pallet {
 			Ok(dao.dao_account_id)
 		}
 
+		pub fn try_get_sub_account(
+			dao_id: <T as pallet::Config>::DaoId,
+			index: u32,
+		) -> result::Result<T::AccountId, DispatchError> {
+			ensure!(
+				SubAccounts::<T>::contains_key(dao_id, index),
+				Error::<T>::SubAccountNotAllowed
+			);
+			let concrete_id = Self::try_get_concrete_id(dao_id)?;
+			Ok(concrete_id.into_sub_account(index))
+		}
+
+		pub fn try_get_status(
+			dao_id: <T as pallet::Config>::DaoId,
+		) -> result::Result<Status, DispatchError> {
+			let dao = Daos::<T>::get(dao_id).ok_or(Error::<T>::DaoNotExists)?;
+			Ok(dao.status)
+		}
+
+		pub fn ensure_active(dao_id: <T as pallet::Config>::DaoId) -> DispatchResult {
+			match Self::try_get_status(dao_id)? {
+				Status::Active => Ok(()),
+				Status::InActive => Err(Error::<T>::DaoNotActive.into()),
+				Status::Dissolved => Err(Error::<T>::DaoDissolved.into()),
+			}
+		}
+
+		/// A suspended DAO can only dispatch `resume_dao` for itself.
+		pub fn ensure_can_dispatch(
+			dao_id: <T as pallet::Config>::DaoId,
+			call: &<T as pallet::Config>::Call,
+		) -> DispatchResult {
+			match Self::try_get_status(dao_id)? {
+				Status::InActive => match call.is_sub_type() {
+					Some(Call::resume_dao { dao_id: id }) if *id == dao_id => Ok(()),
+					_ => Err(Error::<T>::DaoNotActive.into()),
+				},
+				_ => Self::ensure_active(dao_id),
+			}
+		}
+
+		/// The call list of the DAO narrows the calls allowed by `BaseCallFilter`.
+		///
+		/// The calls that set the call list of the DAO are always allowed, so the DAO can not
+		/// lock itself out.
+		pub fn ensure_call_allowed(
+			dao_id: <T as pallet::Config>::DaoId,
+			call: &<T as pallet::Config>::Call,
+		) -> DispatchResult {
+			let list_of = match call.is_sub_type() {
+				Some(Call::set_call_list_mode { dao_id: id, .. }) => Some(*id),
+				Some(Call::set_call_listed { dao_id: id, .. }) => Some(*id),
+				_ => None,
+			};
+			if list_of == Some(dao_id) {
+				return Ok(())
+			}
+			let listed = T::CallId::try_from(call.clone())
+				.map_or(false, |call_id| CallList::<T>::contains_key(dao_id, call_id));
+			let allowed = match CallListModeOf::<T>::get(dao_id) {
+				CallListMode::Deny => !listed,
+				CallListMode::Allow => listed,
+			};
+			ensure!(allowed, Error::<T>::CallNotAllowed);
+			Ok(())
+		}
+
+		/// All DAOs created by the account.
+		pub fn daos_of(creator: &T::AccountId) -> Vec<T::DaoId> {
+			Daos::<T>::iter()
+				.filter(|(_, dao)| &dao.creator == creator)
+				.map(|(dao_id, _)| dao_id)
+				.collect()
+		}
+
+		/// All child DAOs of the DAO.
+		pub fn children_of(dao_id: <T as pallet::Config>::DaoId) -> Vec<T::DaoId> {
+			Children::<T>::iter_key_prefix(dao_id).collect()
+		}
+
+		/// Whether the account is the DAO account of the parent of the DAO.
+		///
+		/// Only the direct parent is checked, the DAOs above it are not the roots of this DAO.
+		pub fn is_parent_account(dao_id: <T as pallet::Config>::DaoId, who: &T::AccountId) -> bool {
+			Daos::<T>::get(dao_id)
+				.and_then(|dao| dao.parent)
+				.and_then(|parent| Self::try_get_dao_account_id(parent).ok())
+				.map_or(false, |account| &account == who)
+		}
+
+		/// The DAO account of the DAO or of its parent.
 		pub fn ensrue_dao_root(
 			o: OriginFor<T>,
 			dao_id: T::DaoId,
 		) -> result::Result<T::AccountId, DispatchError> {
 			let who = ensure_signed(o)?;
-			let dao_id = Self::try_get_dao_account_id(dao_id)?;
-			ensure!(who == dao_id, Error::<T>::BadOrigin);
+			let dao_account_id = Self::try_get_dao_account_id(dao_id)?;
+			ensure!(
+				who == dao_account_id || Self::is_parent_account(dao_id, &who),
+				Error::<T>::BadOrigin
+			);
+			Ok(who)
+		}
+
+		/// The DAO account of the parent of the DAO.
+		pub fn ensure_parent_root(
+			o: OriginFor<T>,
+			dao_id: T::DaoId,
+		) -> result::Result<T::AccountId, DispatchError> {
+			let who = ensure_signed(o)?;
+			ensure!(Self::is_parent_account(dao_id, &who), Error::<T>::BadOrigin);
 			Ok(who)
 		}
 	}
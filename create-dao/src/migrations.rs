@@ -0,0 +1,66 @@
+use super::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{OnRuntimeUpgrade, StorageVersion},
+};
+use sp_runtime::traits::Zero;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+
+/// Add the creation deposit and the parent to `DaoInfo`, and map the specific groups of the
+/// existing DAOs to them.
+pub mod v1 {
+	use super::*;
+
+	/// `DaoInfo` before the version 1.
+	#[derive(Encode, Decode)]
+	pub struct OldDaoInfo<AccountId, ConcreteId> {
+		pub creator: AccountId,
+		pub start_block: u32,
+		pub concrete_id: ConcreteId,
+		pub dao_account_id: AccountId,
+		pub status: Status,
+	}
+
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 0 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			Daos::<T>::translate::<OldDaoInfo<T::AccountId, T::ConcreteId>, _>(|dao_id, old| {
+				translated += 1;
+				if !DaoIdOf::<T>::contains_key(old.concrete_id) {
+					DaoIdOf::<T>::insert(old.concrete_id, dao_id);
+				}
+				Some(DaoInfo {
+					creator: old.creator,
+					start_block: old.start_block.into(),
+					concrete_id: old.concrete_id,
+					dao_account_id: old.dao_account_id,
+					status: old.status,
+					deposit: Zero::zero(),
+					parent: None,
+				})
+			});
+			StorageVersion::new(1).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(translated * 2 + 1, translated * 2 + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			Ok((Daos::<T>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let count = u32::decode(&mut &state[..]).map_err(|_| "The state is not a count.")?;
+			ensure!(Daos::<T>::iter().count() as u32 == count, "Some DAOs are not migrated.");
+			ensure!(StorageVersion::get::<Pallet<T>>() == 1, "The storage version is not 1.");
+			Ok(())
+		}
+	}
+}
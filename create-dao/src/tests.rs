@@ -1,21 +1,29 @@
 #![allow(unused_imports)]
 #![cfg(test)]
 use super::*;
-use crate::mock::*;
-use frame_support::{assert_noop, assert_ok, debug, log::debug};
-use primitives::ids::Nft;
+use crate::mock::{Call, *};
+use frame_support::{
+	assert_noop, assert_ok, debug,
+	log::debug,
+	storage::unhashed,
+	traits::{OnRuntimeUpgrade, StorageVersion},
+};
+use primitives::{ids::Nft, types::CallId};
+use sp_core::H256;
+use sp_runtime::BuildStorage;
 
 pub const ALICE: u64 = 1;
 
 pub fn create_dao() {
-	Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 4]).unwrap();
+	Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 4], None).unwrap();
 }
 
 #[test]
 pub fn create_dao_should_work() {
 	new_test_ext().execute_with(|| {
-		assert!(Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 60]).is_err());
-		assert_ok!(Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 4]));
+		assert!(Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 60], None)
+			.is_err());
+		assert_ok!(Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 4], None));
 		assert!(Daos::<Test>::get(0u64).is_some());
 		assert!(NextDaoId::<Test>::get() == 1u64);
 	});
@@ -69,3 +77,462 @@ pub fn get_dao_account_id() {
 		assert_ok!(Pallet::<Test>::try_get_dao_account_id(0u64));
 	});
 }
+
+#[test]
+pub fn dao_lifecycle_should_work() {
+	new_test_ext().execute_with(|| {
+		create_dao();
+		let dao_account = Daos::<Test>::get(0u64).unwrap().dao_account_id;
+		assert!(Pallet::<Test>::suspend_dao(Origin::signed(ALICE), 0u64).is_err());
+		assert_noop!(
+			Pallet::<Test>::resume_dao(Origin::signed(dao_account), 0u64),
+			Error::<Test>::DaoNotSuspended
+		);
+
+		assert_ok!(Pallet::<Test>::suspend_dao(Origin::signed(dao_account), 0u64));
+		assert_eq!(Pallet::<Test>::try_get_status(0u64), Ok(Status::InActive));
+		assert_noop!(Pallet::<Test>::ensure_active(0u64), Error::<Test>::DaoNotActive);
+		let remark = Call::DAO(crate::Call::dao_remark { dao_id: 0u64, remark: vec![1; 10] });
+		assert_noop!(
+			Pallet::<Test>::ensure_can_dispatch(0u64, &remark),
+			Error::<Test>::DaoNotActive
+		);
+		assert_ok!(Pallet::<Test>::ensure_can_dispatch(
+			0u64,
+			&Call::DAO(crate::Call::resume_dao { dao_id: 0u64 })
+		));
+
+		assert_ok!(Pallet::<Test>::resume_dao(Origin::signed(dao_account), 0u64));
+		assert_ok!(Pallet::<Test>::ensure_can_dispatch(0u64, &remark));
+
+		assert_ok!(Pallet::<Test>::dissolve_dao(Origin::signed(dao_account), 0u64));
+		assert_eq!(Pallet::<Test>::try_get_status(0u64), Ok(Status::Dissolved));
+		assert_noop!(
+			Pallet::<Test>::dissolve_dao(Origin::signed(dao_account), 0u64),
+			Error::<Test>::DaoDissolved
+		);
+		assert_noop!(
+			Pallet::<Test>::resume_dao(Origin::signed(dao_account), 0u64),
+			Error::<Test>::DaoNotSuspended
+		);
+		assert_noop!(
+			Pallet::<Test>::ensure_can_dispatch(0u64, &remark),
+			Error::<Test>::DaoDissolved
+		);
+	});
+}
+
+#[test]
+pub fn set_metadata_should_work() {
+	new_test_ext().execute_with(|| {
+		create_dao();
+		assert_eq!(Pallet::<Test>::metadata_of(0u64).description.into_inner(), vec![1; 4]);
+		let dao_account = Daos::<Test>::get(0u64).unwrap().dao_account_id;
+		assert!(Pallet::<Test>::set_metadata(
+			Origin::signed(ALICE),
+			0u64,
+			vec![1; 10],
+			vec![2; 10],
+			vec![3; 10],
+			None
+		)
+		.is_err());
+		assert_noop!(
+			Pallet::<Test>::set_metadata(
+				Origin::signed(dao_account),
+				0u64,
+				vec![1; 60],
+				vec![2; 10],
+				vec![3; 10],
+				None
+			),
+			Error::<Test>::NameTooLong
+		);
+		assert_ok!(Pallet::<Test>::set_metadata(
+			Origin::signed(dao_account),
+			0u64,
+			vec![1; 10],
+			vec![2; 10],
+			vec![3; 10],
+			Some(H256::repeat_byte(4))
+		));
+		let metadata = Pallet::<Test>::metadata_of(0u64);
+		assert_eq!(metadata.name.into_inner(), vec![1; 10]);
+		assert_eq!(metadata.description.into_inner(), vec![2; 10]);
+		assert_eq!(metadata.website.into_inner(), vec![3; 10]);
+		assert_eq!(metadata.logo, Some(H256::repeat_byte(4)));
+	});
+}
+
+#[test]
+pub fn concrete_id_should_map_to_one_dao() {
+	new_test_ext().execute_with(|| {
+		assert!(Pallet::<Test>::try_get_dao_id(Nft(0u64)).is_err());
+		create_dao();
+		assert_eq!(Pallet::<Test>::try_get_dao_id(Nft(0u64)), Ok(0u64));
+		assert_noop!(
+			Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 4], None),
+			Error::<Test>::DaoExists
+		);
+		assert_ok!(Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(1u64), vec![1; 4], None));
+		assert_eq!(Pallet::<Test>::try_get_dao_id(Nft(1u64)), Ok(1u64));
+
+		let dao_account = Daos::<Test>::get(0u64).unwrap().dao_account_id;
+		assert_ok!(Pallet::<Test>::dissolve_dao(Origin::signed(dao_account), 0u64));
+		assert_eq!(Pallet::<Test>::try_get_dao_id(Nft(0u64)), Ok(0u64));
+		assert_noop!(
+			Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 4], None),
+			Error::<Test>::DaoExists
+		);
+	});
+}
+
+#[test]
+pub fn creation_deposit_should_work() {
+	new_test_ext().execute_with(|| {
+		create_dao();
+		assert_eq!(Balances::reserved_balance(ALICE), 5u64);
+		assert_eq!(Daos::<Test>::get(0u64).unwrap().deposit, 5u64);
+		let dao_account = Daos::<Test>::get(0u64).unwrap().dao_account_id;
+		assert_ok!(Pallet::<Test>::dissolve_dao(Origin::signed(dao_account), 0u64));
+		assert_eq!(Balances::reserved_balance(ALICE), 0u64);
+		assert_eq!(Balances::free_balance(ALICE), 100u64);
+
+		assert_noop!(
+			Pallet::<Test>::create_dao(Origin::signed(3u64), Nft(3u64), vec![1; 4], None),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+pub fn slash_dao_should_work() {
+	new_test_ext().execute_with(|| {
+		create_dao();
+		assert!(Pallet::<Test>::slash_dao(Origin::signed(ALICE), 0u64).is_err());
+		assert_ok!(Pallet::<Test>::slash_dao(Origin::root(), 0u64));
+		assert_eq!(Pallet::<Test>::try_get_status(0u64), Ok(Status::Dissolved));
+		assert_eq!(Balances::reserved_balance(ALICE), 0u64);
+		assert_eq!(Balances::free_balance(ALICE), 95u64);
+		assert_noop!(Pallet::<Test>::slash_dao(Origin::root(), 0u64), Error::<Test>::DaoDissolved);
+	});
+}
+
+#[test]
+pub fn only_owner_can_create_dao() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Pallet::<Test>::create_dao(Origin::signed(2u64), Nft(0u64), vec![1; 4], None),
+			Error::<Test>::HaveNoCreatePermission
+		);
+		assert_noop!(
+			Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(3u64), vec![1; 4], None),
+			Error::<Test>::HaveNoCreatePermission
+		);
+		assert_ok!(Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 4], None));
+	});
+}
+
+#[test]
+pub fn sub_account_should_work() {
+	new_test_ext().execute_with(|| {
+		create_dao();
+		let dao_account = Daos::<Test>::get(0u64).unwrap().dao_account_id;
+		assert_noop!(
+			Pallet::<Test>::try_get_sub_account(0u64, 1u32),
+			Error::<Test>::SubAccountNotAllowed
+		);
+		assert!(Pallet::<Test>::set_sub_account(Origin::signed(ALICE), 0u64, 1u32, true).is_err());
+		assert_ok!(Pallet::<Test>::set_sub_account(Origin::signed(dao_account), 0u64, 1u32, true));
+
+		let sub_account = Pallet::<Test>::try_get_sub_account(0u64, 1u32).unwrap();
+		assert_ne!(sub_account, dao_account);
+		assert_eq!(sub_account, Nft(0u64).into_sub_account(1u32));
+
+		let remark = Call::System(frame_system::Call::remark { remark: vec![1; 10] });
+		assert_noop!(
+			Pallet::<Test>::dispatch_as_sub_account(
+				Origin::signed(dao_account),
+				0u64,
+				1u32,
+				Box::new(remark)
+			),
+			Error::<Test>::InVailCall
+		);
+
+		assert_ok!(Pallet::<Test>::set_sub_account(Origin::signed(dao_account), 0u64, 1u32, false));
+		assert!(Pallet::<Test>::try_get_sub_account(0u64, 1u32).is_err());
+	});
+}
+
+#[test]
+pub fn sub_accounts_of_different_daos_should_differ() {
+	new_test_ext().execute_with(|| {
+		create_dao();
+		assert_ok!(Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(1u64), vec![1; 4], None));
+		for dao_id in [0u64, 1u64] {
+			let dao_account = Daos::<Test>::get(dao_id).unwrap().dao_account_id;
+			assert_ok!(Pallet::<Test>::set_sub_account(
+				Origin::signed(dao_account),
+				dao_id,
+				0u32,
+				true
+			));
+		}
+		let sub_account_0 = Pallet::<Test>::try_get_sub_account(0u64, 0u32).unwrap();
+		let sub_account_1 = Pallet::<Test>::try_get_sub_account(1u64, 0u32).unwrap();
+		assert_ne!(sub_account_0, sub_account_1);
+		assert_ne!(sub_account_0, Nft(0u64).into_sub_account(1u32));
+		assert_ne!(sub_account_0, Daos::<Test>::get(0u64).unwrap().dao_account_id);
+	});
+}
+
+pub fn get_template() -> DaoTemplateOf<Test> {
+	DaoTemplate {
+		launch_period: Some(10u64),
+		voting_period: None,
+		reserve_period: None,
+		enactment_period: None,
+		minimum_deposit: Some(1u64),
+		motion_duration: None,
+		ensure_origins: Default::default(),
+		emergency_pledge: None,
+		sudo: true,
+	}
+}
+
+#[test]
+pub fn template_should_work() {
+	new_test_ext().execute_with(|| {
+		assert!(Pallet::<Test>::register_template(Origin::signed(ALICE), get_template()).is_err());
+		assert_ok!(Pallet::<Test>::register_template(Origin::root(), get_template()));
+		assert_eq!(Pallet::<Test>::templates(0u32), Some(get_template()));
+		assert_eq!(Pallet::<Test>::next_template_id(), 1u32);
+
+		assert_noop!(
+			Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 4], Some(1u32)),
+			Error::<Test>::TemplateNotExists
+		);
+		assert_ok!(Pallet::<Test>::create_dao(
+			Origin::signed(ALICE),
+			Nft(0u64),
+			vec![1; 4],
+			Some(0u32)
+		));
+
+		assert_ok!(Pallet::<Test>::remove_template(Origin::root(), 0u32));
+		assert!(Pallet::<Test>::templates(0u32).is_none());
+		assert_noop!(
+			Pallet::<Test>::remove_template(Origin::root(), 0u32),
+			Error::<Test>::TemplateNotExists
+		);
+		assert!(Daos::<Test>::get(0u64).is_some());
+	});
+}
+
+#[test]
+pub fn sub_dao_should_work() {
+	new_test_ext().execute_with(|| {
+		create_dao();
+		assert_ok!(Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(1u64), vec![1; 4], None));
+		let parent_account = Daos::<Test>::get(0u64).unwrap().dao_account_id;
+		let child_account = Daos::<Test>::get(1u64).unwrap().dao_account_id;
+
+		assert_noop!(
+			Pallet::<Test>::set_parent(Origin::signed(parent_account), 1u64, Some(0u64)),
+			Error::<Test>::BadOrigin
+		);
+		assert_noop!(
+			Pallet::<Test>::set_parent(Origin::signed(child_account), 1u64, Some(1u64)),
+			Error::<Test>::ParentCycle
+		);
+		assert_ok!(Pallet::<Test>::set_parent(Origin::signed(child_account), 1u64, Some(0u64)));
+		assert_eq!(Pallet::<Test>::pending_parent(1u64), Some(0u64));
+		assert!(Pallet::<Test>::children_of(0u64).is_empty());
+		assert_noop!(
+			Pallet::<Test>::accept_parent(Origin::signed(child_account), 1u64),
+			Error::<Test>::NotPendingParent
+		);
+		assert_ok!(Pallet::<Test>::accept_parent(Origin::signed(parent_account), 1u64));
+		assert_eq!(Pallet::<Test>::pending_parent(1u64), None);
+		assert_eq!(Pallet::<Test>::children_of(0u64), vec![1u64]);
+		assert_eq!(Daos::<Test>::get(1u64).unwrap().parent, Some(0u64));
+		assert_ok!(Pallet::<Test>::ensrue_dao_root(Origin::signed(parent_account), 1u64));
+		assert_noop!(
+			Pallet::<Test>::set_parent(Origin::signed(parent_account), 0u64, Some(1u64)),
+			Error::<Test>::ParentCycle
+		);
+		assert_noop!(
+			Pallet::<Test>::set_parent(Origin::signed(child_account), 1u64, None),
+			Error::<Test>::BadOrigin
+		);
+
+		assert_ok!(Pallet::<Test>::suspend_dao(Origin::signed(parent_account), 1u64));
+		assert_noop!(
+			Pallet::<Test>::resume_dao(Origin::signed(child_account), 1u64),
+			Error::<Test>::SuspendedByParent
+		);
+		assert_ok!(Pallet::<Test>::resume_dao(Origin::signed(parent_account), 1u64));
+
+		assert_ok!(Pallet::<Test>::set_parent(Origin::signed(parent_account), 1u64, None));
+		assert!(Pallet::<Test>::children_of(0u64).is_empty());
+		assert_eq!(Daos::<Test>::get(1u64).unwrap().parent, None);
+		assert_noop!(
+			Pallet::<Test>::accept_parent(Origin::signed(parent_account), 1u64),
+			Error::<Test>::NotPendingParent
+		);
+		assert_ok!(Pallet::<Test>::set_parent(Origin::signed(child_account), 1u64, Some(0u64)));
+		assert_ok!(Pallet::<Test>::accept_parent(Origin::signed(parent_account), 1u64));
+
+		assert_ok!(Pallet::<Test>::dissolve_dao(Origin::signed(parent_account), 0u64));
+		assert!(Pallet::<Test>::children_of(0u64).is_empty());
+		assert_eq!(Daos::<Test>::get(1u64).unwrap().parent, None);
+	});
+}
+
+#[test]
+pub fn daos_of_should_work() {
+	new_test_ext().execute_with(|| {
+		create_dao();
+		assert_ok!(Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(1u64), vec![1; 4], None));
+		let mut daos = Pallet::<Test>::daos_of(&ALICE);
+		daos.sort();
+		assert_eq!(daos, vec![0u64, 1u64]);
+		assert!(Pallet::<Test>::daos_of(&2u64).is_empty());
+	});
+}
+
+#[test]
+pub fn genesis_config_should_work() {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	crate::GenesisConfig::<Test> { daos: vec![(ALICE, Nft(0u64), vec![1; 4])] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		assert_eq!(Pallet::<Test>::try_get_creator(0u64), Ok(ALICE));
+		assert_eq!(Pallet::<Test>::try_get_status(0u64), Ok(Status::Active));
+		assert_eq!(DaoIdOf::<Test>::get(Nft(0u64)), Some(0u64));
+		assert_eq!(Pallet::<Test>::metadata_of(0u64).description.into_inner(), vec![1; 4]);
+		assert_eq!(NextDaoId::<Test>::get(), 1u64);
+	});
+}
+
+#[test]
+pub fn call_id_should_work() {
+	let remark = Call::DAO(crate::Call::dao_remark { dao_id: 0u64, remark: vec![1; 10] });
+	let suspend = Call::DAO(crate::Call::suspend_dao { dao_id: 1u64 });
+	assert_eq!(CallId::try_from(remark), Ok(CallId::new(2, 1)));
+	assert_eq!(CallId::try_from(suspend), Ok(CallId::new(2, 2)));
+	assert_eq!(
+		CallId::try_from(Call::System(frame_system::Call::remark { remark: vec![] })),
+		Ok(CallId::new(0, 0))
+	);
+}
+
+#[test]
+pub fn call_list_should_work() {
+	new_test_ext().execute_with(|| {
+		create_dao();
+		let dao_account = Daos::<Test>::get(0u64).unwrap().dao_account_id;
+		let remark = Call::DAO(crate::Call::dao_remark { dao_id: 0u64, remark: vec![1; 10] });
+		let remark_id = CallId::try_from(remark.clone()).unwrap();
+		assert_ok!(Pallet::<Test>::ensure_call_allowed(0u64, &remark));
+		assert!(
+			Pallet::<Test>::set_call_listed(Origin::signed(ALICE), 0u64, remark_id, true).is_err()
+		);
+		assert_ok!(Pallet::<Test>::set_call_listed(
+			Origin::signed(dao_account),
+			0u64,
+			remark_id,
+			true
+		));
+		assert_noop!(
+			Pallet::<Test>::ensure_call_allowed(0u64, &remark),
+			Error::<Test>::CallNotAllowed
+		);
+
+		assert_ok!(Pallet::<Test>::set_call_list_mode(
+			Origin::signed(dao_account),
+			0u64,
+			CallListMode::Allow
+		));
+		assert_ok!(Pallet::<Test>::ensure_call_allowed(0u64, &remark));
+		let suspend = Call::DAO(crate::Call::suspend_dao { dao_id: 0u64 });
+		assert_noop!(
+			Pallet::<Test>::ensure_call_allowed(0u64, &suspend),
+			Error::<Test>::CallNotAllowed
+		);
+		let set_mode =
+			Call::DAO(crate::Call::set_call_list_mode { dao_id: 0u64, mode: CallListMode::Deny });
+		assert_ok!(Pallet::<Test>::ensure_call_allowed(0u64, &set_mode));
+
+		assert_ok!(Pallet::<Test>::dissolve_dao(Origin::signed(dao_account), 0u64));
+		assert_eq!(Pallet::<Test>::call_list_mode(0u64), CallListMode::Deny);
+		assert!(Pallet::<Test>::call_list(0u64, remark_id).is_none());
+	});
+}
+
+#[test]
+pub fn transfer_creator_should_work() {
+	new_test_ext().execute_with(|| {
+		create_dao();
+		let dao_account = Daos::<Test>::get(0u64).unwrap().dao_account_id;
+		assert!(Pallet::<Test>::transfer_creator(Origin::signed(ALICE), 0u64, Some(3u64)).is_err());
+		assert_ok!(Pallet::<Test>::transfer_creator(Origin::signed(dao_account), 0u64, Some(3u64)));
+		assert_noop!(
+			Pallet::<Test>::accept_creator(Origin::signed(3u64), 0u64),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+
+		assert_ok!(Pallet::<Test>::transfer_creator(Origin::signed(dao_account), 0u64, Some(2u64)));
+		assert_eq!(Pallet::<Test>::pending_creator(0u64), Some(2u64));
+		assert_noop!(
+			Pallet::<Test>::accept_creator(Origin::signed(3u64), 0u64),
+			Error::<Test>::NotPendingCreator
+		);
+		assert_ok!(Pallet::<Test>::accept_creator(Origin::signed(2u64), 0u64));
+		assert_eq!(Pallet::<Test>::try_get_creator(0u64), Ok(2u64));
+		assert_eq!(Pallet::<Test>::pending_creator(0u64), None);
+		assert_eq!(Balances::reserved_balance(ALICE), 0u64);
+		assert_eq!(Balances::reserved_balance(2u64), 5u64);
+
+		assert_ok!(Pallet::<Test>::transfer_creator(Origin::signed(dao_account), 0u64, Some(3u64)));
+		assert_ok!(Pallet::<Test>::transfer_creator(Origin::signed(dao_account), 0u64, None));
+		assert_noop!(
+			Pallet::<Test>::accept_creator(Origin::signed(3u64), 0u64),
+			Error::<Test>::NotPendingCreator
+		);
+
+		assert_ok!(Pallet::<Test>::dissolve_dao(Origin::signed(dao_account), 0u64));
+		assert_eq!(Balances::reserved_balance(2u64), 0u64);
+	});
+}
+
+#[test]
+pub fn migrate_to_v1_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<Pallet<Test>>();
+		unhashed::put(
+			&Daos::<Test>::hashed_key_for(0u64),
+			&migrations::v1::OldDaoInfo {
+				creator: ALICE,
+				start_block: 5u32,
+				concrete_id: Nft(0u64),
+				dao_account_id: Nft(0u64).into_account(),
+				status: Status::Active,
+			},
+		);
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<Pallet<Test>>(), 1);
+		let dao = Daos::<Test>::get(0u64).unwrap();
+		assert_eq!(dao.start_block, 5u64);
+		assert_eq!(dao.deposit, 0u64);
+		assert_eq!(dao.parent, None);
+		assert_eq!(Pallet::<Test>::try_get_creator(0u64), Ok(ALICE));
+		assert_eq!(DaoIdOf::<Test>::get(Nft(0u64)), Some(0u64));
+		assert_noop!(
+			Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 4], None),
+			Error::<Test>::DaoExists
+		);
+	});
+}
@@ -31,12 +31,30 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
     fn create_dao() -> Weight;
     fn dao_remark() -> Weight;
+    fn suspend_dao() -> Weight;
+    fn resume_dao() -> Weight;
+    fn dissolve_dao() -> Weight;
+    fn slash_dao() -> Weight;
+    fn set_metadata() -> Weight;
+    fn set_sub_account() -> Weight;
+    fn dispatch_as_sub_account() -> Weight;
+    fn register_template() -> Weight;
+    fn remove_template() -> Weight;
+    fn set_parent() -> Weight;
+    fn set_call_list_mode() -> Weight;
+    fn set_call_listed() -> Weight;
+    fn transfer_creator() -> Weight;
+    fn accept_creator() -> Weight;
+    fn accept_parent() -> Weight;
 }
 
 /// Weights for daos_create_dao using the Substrate node and recommended hardware.
 pub struct DaosWeight<T>(PhantomData<T>);
         impl<T: frame_system::Config> WeightInfo for DaosWeight<T> {
+            // Storage: CreateDao DaoIdOf (r:1 w:1)
+            // Storage: CreateDao Templates (r:1 w:0)
             // Storage: CreateDao NextDaoId (r:1 w:1)
+            // Storage: System Account (r:1 w:1)
             // Storage: DaoSudo Account (r:0 w:1)
             // Storage: CreateDao Daos (r:0 w:1)
         fn create_dao() -> Weight {
@@ -51,11 +69,93 @@ pub struct DaosWeight<T>(PhantomData<T>);
         // (11_086_000 as Weight)
         //     .saturating_add(T::DbWeight::get().reads(1 as Weight))
         }
+            // Storage: CreateDao Daos (r:1 w:1)
+        fn suspend_dao() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:1)
+        fn resume_dao() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:1)
+            // Storage: CreateDao DaoIdOf (r:0 w:1)
+            // Storage: System Account (r:1 w:1)
+        fn dissolve_dao() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:1)
+            // Storage: CreateDao DaoIdOf (r:0 w:1)
+            // Storage: System Account (r:1 w:1)
+        fn slash_dao() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: CreateDao MetadataOf (r:0 w:1)
+        fn set_metadata() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: CreateDao SubAccounts (r:0 w:1)
+        fn set_sub_account() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: CreateDao SubAccounts (r:1 w:0)
+        fn dispatch_as_sub_account() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao NextTemplateId (r:1 w:1)
+            // Storage: CreateDao Templates (r:0 w:1)
+        fn register_template() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Templates (r:1 w:1)
+        fn remove_template() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:2 w:1)
+            // Storage: CreateDao PendingParent (r:0 w:1)
+            // Storage: CreateDao Children (r:0 w:1)
+            // Storage: CreateDao SuspendedByParent (r:0 w:1)
+        fn set_parent() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: CreateDao CallListModeOf (r:0 w:1)
+        fn set_call_list_mode() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: CreateDao CallList (r:0 w:1)
+        fn set_call_listed() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:2 w:0)
+            // Storage: CreateDao PendingCreator (r:0 w:1)
+        fn transfer_creator() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao PendingCreator (r:1 w:1)
+            // Storage: CreateDao Daos (r:1 w:1)
+            // Storage: System Account (r:2 w:2)
+        fn accept_creator() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao PendingParent (r:1 w:1)
+            // Storage: CreateDao Daos (r:3 w:1)
+            // Storage: CreateDao Children (r:0 w:2)
+            // Storage: CreateDao SuspendedByParent (r:0 w:1)
+        fn accept_parent() -> Weight {
+                Weight::from_all(2000_0000)
+        }
     }
 
     // For backwards compatibility and tests
     impl WeightInfo for () {
+            // Storage: CreateDao DaoIdOf (r:1 w:1)
+            // Storage: CreateDao Templates (r:1 w:0)
             // Storage: CreateDao NextDaoId (r:1 w:1)
+            // Storage: System Account (r:1 w:1)
             // Storage: DaoSudo Account (r:0 w:1)
             // Storage: CreateDao Daos (r:0 w:1)
         fn create_dao() -> Weight {
@@ -70,4 +170,83 @@ pub struct DaosWeight<T>(PhantomData<T>);
         // (11_086_000 as Weight)
         //     .saturating_add(RocksDbWeight::get().reads(1 as Weight))
         }
+            // Storage: CreateDao Daos (r:1 w:1)
+        fn suspend_dao() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:1)
+        fn resume_dao() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:1)
+            // Storage: CreateDao DaoIdOf (r:0 w:1)
+            // Storage: System Account (r:1 w:1)
+        fn dissolve_dao() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:1)
+            // Storage: CreateDao DaoIdOf (r:0 w:1)
+            // Storage: System Account (r:1 w:1)
+        fn slash_dao() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: CreateDao MetadataOf (r:0 w:1)
+        fn set_metadata() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: CreateDao SubAccounts (r:0 w:1)
+        fn set_sub_account() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: CreateDao SubAccounts (r:1 w:0)
+        fn dispatch_as_sub_account() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao NextTemplateId (r:1 w:1)
+            // Storage: CreateDao Templates (r:0 w:1)
+        fn register_template() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Templates (r:1 w:1)
+        fn remove_template() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:2 w:1)
+            // Storage: CreateDao PendingParent (r:0 w:1)
+            // Storage: CreateDao Children (r:0 w:1)
+            // Storage: CreateDao SuspendedByParent (r:0 w:1)
+        fn set_parent() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: CreateDao CallListModeOf (r:0 w:1)
+        fn set_call_list_mode() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: CreateDao CallList (r:0 w:1)
+        fn set_call_listed() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:2 w:0)
+            // Storage: CreateDao PendingCreator (r:0 w:1)
+        fn transfer_creator() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao PendingCreator (r:1 w:1)
+            // Storage: CreateDao Daos (r:1 w:1)
+            // Storage: System Account (r:2 w:2)
+        fn accept_creator() -> Weight {
+                Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao PendingParent (r:1 w:1)
+            // Storage: CreateDao Daos (r:3 w:1)
+            // Storage: CreateDao Children (r:0 w:2)
+            // Storage: CreateDao SuspendedByParent (r:0 w:1)
+        fn accept_parent() -> Weight {
+                Weight::from_all(2000_0000)
+        }
     }
\ No newline at end of file
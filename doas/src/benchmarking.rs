@@ -2,17 +2,19 @@
 #![allow(dead_code)]
 use super::*;
 use crate::{Config, Pallet as DoAs};
-use dao::{Call as DaoCall, Pallet as Dao};
+use dao::{Call as DaoCall, Currency, Pallet as Dao};
 use frame_benchmarking::{
 	account, benchmarks, benchmarks_instance, impl_benchmark_test_suite, whitelisted_caller,
 };
 use frame_system::RawOrigin as SystemOrigin;
 use primitives::{types::ProposalIndex, AccountIdConversion};
-use sp_runtime::SaturatedConversion;
+use sp_runtime::{traits::Bounded, SaturatedConversion};
 use sp_std::vec;
 
 fn get_alice<T: Config>() -> T::AccountId {
-	account("alice", 1, 1)
+	let alice = account("alice", 1, 1);
+	<T as dao::Config>::Currency::make_free_balance_be(&alice, dao::BalanceOf::<T>::max_value());
+	alice
 }
 
 fn get_dao_account<T: Config>(second_id: T::ConcreteId) -> T::AccountId {
@@ -23,7 +25,8 @@ fn creat_dao<T: Config>() -> (T::DaoId, T::ConcreteId) {
 	let alice = get_alice::<T>();
 	let dao_id = T::DaoId::default();
 	let second_id: T::ConcreteId = Default::default();
-	assert!(Dao::<T>::create_dao(SystemOrigin::Signed(alice).into(), second_id, vec![1; 4]).is_ok());
+	assert!(Dao::<T>::create_dao(SystemOrigin::Signed(alice).into(), second_id, vec![1; 4], None)
+		.is_ok());
 	(dao_id, second_id)
 }
 
@@ -91,8 +91,12 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::event]
@@ -109,6 +113,7 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// The agency execute an external call
+		#[pallet::call_index(0)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::do_as_agency())]
 		pub fn do_as_agency(
 			origin: OriginFor<T>,
@@ -119,6 +124,8 @@ pub mod pallet {
 				dao::Pallet::<T>::try_get_concrete_id(dao_id)?.contains(*call.clone()),
 				dao::Error::<T>::InVailCall
 			);
+			dao::Pallet::<T>::ensure_call_allowed(dao_id, &call)?;
+			dao::Pallet::<T>::ensure_can_dispatch(dao_id, &call)?;
 			let call_id: T::CallId =
 				TryFrom::<<T as dao::Config>::Call>::try_from(*call.clone()).unwrap_or_default();
 
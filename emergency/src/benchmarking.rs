@@ -15,19 +15,28 @@ const DOLLARS: u128 = 1_00_00000_00000;
 
 fn get_alice<T: Config>() -> T::AccountId {
 	let alice = account("alice", 1, 1);
-	T::Currency::deposit_creating(&alice, (100000 * DOLLARS).saturated_into::<BalanceOf<T>>());
+	<T as Config>::Currency::deposit_creating(
+		&alice,
+		(100000 * DOLLARS).saturated_into::<BalanceOf<T>>(),
+	);
 	alice
 }
 
 fn get_bob<T: Config>() -> T::AccountId {
 	let bob = account("bob", 1, 1);
-	T::Currency::deposit_creating(&bob, (100000 * DOLLARS).saturated_into::<BalanceOf<T>>());
+	<T as Config>::Currency::deposit_creating(
+		&bob,
+		(100000 * DOLLARS).saturated_into::<BalanceOf<T>>(),
+	);
 	bob
 }
 
 fn get_dao_account<T: Config>(second_id: T::ConcreteId) -> T::AccountId {
 	let who = second_id.into_account();
-	T::Currency::deposit_creating(&who, (100000 * DOLLARS).saturated_into::<BalanceOf<T>>());
+	<T as Config>::Currency::deposit_creating(
+		&who,
+		(100000 * DOLLARS).saturated_into::<BalanceOf<T>>(),
+	);
 	who
 }
 
@@ -39,15 +48,16 @@ fn creat_dao<T: Config>() -> (T::DaoId, T::ConcreteId) {
 		SystemOrigin::Signed(alice).into(),
 		second_id,
 		vec![1; 4],
+		None,
 	)
 	.is_ok());
 	(dao_id, second_id)
 }
 
-fn get_call<T: Config>(dao_id: T::DaoId) -> (<T as dao::Config>::Call, T::Hash) {
-	let proposal: <T as dao::Config>::Call =
-		DaoCall::<T>::dao_remark { dao_id, remark: vec![1; 20] }.into();
-	(proposal.clone(), T::Hashing::hash_of(&proposal))
+fn get_call<T: Config>(dao_id: T::DaoId) -> (BoundedCallOf<T>, H256) {
+	let call: CallOf<T> = DaoCall::<T>::dao_remark { dao_id, remark: vec![1; 20] }.into();
+	let proposal = T::Preimages::bound(call).unwrap();
+	(proposal.clone(), proposal.hash())
 }
 
 fn get_members<T: Config>() -> T::DaoId {
@@ -62,27 +72,27 @@ fn get_members<T: Config>() -> T::DaoId {
 	dao_id
 }
 
-// fn external<T: Config>() -> (T::DaoId, T::Hash) {
+// fn external<T: Config>() -> (T::DaoId, H256) {
 // 	let (dao_id, _second_id) = creat_dao::<T>();
 // 	let (proposal, hash) = get_call::<T>(dao_id);
 // 	assert!(Emergency::<T>::external_track(
 // 		SystemOrigin::Root.into(),
 // 		dao_id,
-// 		Box::new(proposal),
+// 		proposal,
 // 		vec![1, 2, 3, 4]
 // 	)
 // 	.is_ok());
 // 	(dao_id, hash)
 // }
 
-fn internal<T: Config>() -> (T::DaoId, T::Hash) {
+fn internal<T: Config>() -> (T::DaoId, H256) {
 	let dao_id = get_members::<T>();
 	let (proposal, hash) = get_call::<T>(dao_id);
-	crate::Members::<T>::insert(dao_id, vec![get_alice::<T>(), ]);
+	crate::Members::<T>::insert(dao_id, BoundedVec::truncate_from(vec![get_alice::<T>()]));
 	assert!(Emergency::<T>::internal_track(
 		SystemOrigin::Signed(get_alice::<T>()).into(),
 		dao_id,
-		Box::new(proposal),
+		proposal,
 		vec![1, 2, 3, 4]
 	)
 	.is_ok());
@@ -104,12 +114,12 @@ benchmarks! {
 	external_track {
 		let (dao_id, second_id) = creat_dao::<T>();
 		let (proposal, hash) = get_call::<T>(dao_id);
-	}:_(SystemOrigin::Root, dao_id, Box::new(proposal), vec![1, 2, 3, 4])
+	}:_(SystemOrigin::Root, dao_id, proposal, vec![1, 2, 3, 4])
 
 	internal_track {
 		let dao_id = get_members::<T>();
 		let (proposal, hash) = get_call::<T>(dao_id);
-	}:_(SystemOrigin::Signed(get_alice::<T>()), dao_id, Box::new(proposal), vec![1, 2, 3, 4])
+	}:_(SystemOrigin::Signed(get_alice::<T>()), dao_id, proposal, vec![1, 2, 3, 4])
 
 	reject {
 		let (dao_id, hash) = internal::<T>();
@@ -117,6 +127,6 @@ benchmarks! {
 
 	enact_proposal {
 		let (dao_id, hash) = internal::<T>();
-		frame_system::Pallet::<T>::set_block_number(T::BlockNumber::from(1000000u32));
+		frame_system::Pallet::<T>::set_block_number(BlockNumberFor::<T>::from(1000000u32));
 	}:_(SystemOrigin::Signed(get_bob::<T>()), dao_id, hash)
 }
@@ -28,46 +28,45 @@
 //! Anyone can reject internal proposals.
 //!
 
-use dao::{Box, Vec};
-use codec::{Decode, Encode};
+use dao::{AfterDissolve, ApplyTemplate, DaoTemplateOf, Vec};
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::traits::UnfilteredDispatchable;
-use sp_runtime::traits::Hash;
 use frame_support::{
-	dispatch::{DispatchResultWithPostInfo},
+	dispatch::{DispatchResultWithPostInfo, GetDispatchInfo},
 	pallet_prelude::*,
-	traits::{Currency, ReservableCurrency},
+	storage::with_storage_layer,
+	traits::{Bounded, Currency, QueryPreimage, ReservableCurrency, StorePreimage},
 	transactional,
 };
 use frame_system::pallet_prelude::*;
 pub use pallet::*;
 use scale_info::TypeInfo;
-use sp_runtime::{
-	traits::{BlockNumberProvider, CheckedAdd},
-	RuntimeDebug,
-};
+use sp_core::H256;
+use sp_runtime::{traits::CheckedAdd, RuntimeDebug};
 use weights::WeightInfo;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 #[cfg(test)]
 mod mock;
+pub mod migrations;
 #[cfg(test)]
 mod tests;
 mod weights;
 
 /// Specific information on emergency proposal.
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
-pub struct ProposalInfo<AccountId, Call, Amount, BlockNumber> {
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct ProposalInfo<AccountId, Call, Amount, BlockNumber, Reason> {
 	/// who initiated the emergency proposal.
 	who: Option<AccountId>,
 	/// Proposal end block height.
 	end_block: BlockNumber,
-	/// proposal.
+	/// proposal, which is stored in the preimages.
 	call: Call,
 	/// The amount that the proposal needs to pledge.
 	pledge: Amount,
 	/// Reason for Proposal.
-	reason: Vec<u8>,
+	reason: Reason,
 }
 
 #[frame_support::pallet]
@@ -77,6 +76,17 @@ pub mod pallet {
 	pub type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+	pub type CallOf<T> = <T as dao::Config>::Call;
+	pub type BoundedCallOf<T> = Bounded<CallOf<T>>;
+
+	pub type ProposalInfoOf<T> = ProposalInfo<
+		<T as frame_system::Config>::AccountId,
+		BoundedCallOf<T>,
+		BalanceOf<T>,
+		BlockNumberFor<T>,
+		BoundedVec<u8, <T as Config>::MaxReasonLen>,
+	>;
+
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	pub trait Config: frame_system::Config + dao::Config {
@@ -87,24 +97,50 @@ pub mod pallet {
 		type ExternalOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 		/// Operations related to funds.
 		type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
+		/// Where the calls of the proposals are stored.
+		type Preimages: QueryPreimage + StorePreimage;
 		/// The minimum pledge amount required by the system. Each DAO cannot be lower than this value.
 		#[pallet::constant]
 		type MinPledge: Get<BalanceOf<Self>>;
 		/// How long the proposal takes.
 		#[pallet::constant]
-		type TrackPeriod: Get<u32>;
+		type TrackPeriod: Get<BlockNumberFor<Self>>;
+		/// The maximum number of emergency members of a DAO.
+		#[pallet::constant]
+		type MaxMembers: Get<u32>;
+		/// The maximum number of emergency proposals of a DAO at one time.
+		#[pallet::constant]
+		type MaxProposals: Get<u32>;
+		/// The maximum length of the reason of an emergency proposal.
+		#[pallet::constant]
+		type MaxReasonLen: Get<u32>;
+		/// The maximum number of proposals that are enacted automatically in one block.
+		#[pallet::constant]
+		type MaxScheduledPerBlock: Get<u32>;
+		/// The maximum weight of the proposals that are enacted automatically in one block, with
+		/// their calls.
+		#[pallet::constant]
+		type MaxScheduledWeight: Get<Weight>;
 		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+
 	#[pallet::pallet]
-	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Members of the DAO who can make emergency proposals.
 	#[pallet::storage]
 	#[pallet::getter(fn members)]
-	pub type Members<T: Config> =
-		StorageMap<_, Identity, <T as dao::Config>::DaoId, Vec<T::AccountId>, ValueQuery>;
+	pub type Members<T: Config> = StorageMap<
+		_,
+		Identity,
+		<T as dao::Config>::DaoId,
+		BoundedVec<T::AccountId, T::MaxMembers>,
+		ValueQuery,
+	>;
 
 	/// The amount that needs to be pledged for internal emergency proposals.
 	#[pallet::storage]
@@ -114,31 +150,61 @@ pub mod pallet {
 	/// hash of all emergency proposals in DAO.
 	#[pallet::storage]
 	#[pallet::getter(fn hashes_of)]
-	pub type HashesOf<T: Config> = StorageMap<_, Identity, T::DaoId, Vec<T::Hash>, ValueQuery>;
+	pub type HashesOf<T: Config> =
+		StorageMap<_, Twox64Concat, T::DaoId, BoundedVec<H256, T::MaxProposals>, ValueQuery>;
 
 	/// Specific information for each proposal.
 	#[pallet::storage]
 	#[pallet::getter(fn proposal_of)]
-	pub type ProposalOf<T: Config> = StorageDoubleMap<
+	pub type ProposalOf<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::DaoId, Blake2_128Concat, H256, ProposalInfoOf<T>>;
+
+	/// The proposals that are enacted automatically at the beginning of each block.
+	#[pallet::storage]
+	#[pallet::getter(fn agenda)]
+	pub type Agenda<T: Config> = StorageMap<
 		_,
-		Identity,
-		T::DaoId,
-		Identity,
-		T::Hash,
-		ProposalInfo<T::AccountId, <T as dao::Config>::Call, BalanceOf<T>, u32>,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<(T::DaoId, H256), T::MaxScheduledPerBlock>,
+		ValueQuery,
 	>;
 
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// The emergency members of each DAO.
+		pub members: Vec<(T::DaoId, Vec<T::AccountId>)>,
+		/// The pledge of the internal proposals of each DAO.
+		pub pledges: Vec<(T::DaoId, BalanceOf<T>)>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			for (dao_id, members) in self.members.iter() {
+				let members: BoundedVec<T::AccountId, T::MaxMembers> =
+					members.clone().try_into().expect("Too many emergency members.");
+				Members::<T>::insert(dao_id, members);
+			}
+			for (dao_id, amount) in self.pledges.iter() {
+				assert!(*amount >= T::MinPledge::get(), "The pledge is lower than the minimum.");
+				PledgeOf::<T>::insert(dao_id, amount);
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// Set members who can make emergency proposals.
 		SetMembers { dao_id: T::DaoId, members: Vec<T::AccountId> },
 		/// Successfully made an emergency proposal.
-		Track { dao_id: T::DaoId, who: Option<T::AccountId>, call: <T as dao::Config>::Call },
+		Track { dao_id: T::DaoId, who: Option<T::AccountId>, call: BoundedCallOf<T> },
 		/// Successfully rejected an emergency proposal.
-		Rejected { dao_id: T::DaoId, proposal_hash: T::Hash },
+		Rejected { dao_id: T::DaoId, proposal_hash: H256 },
 		/// Execute a transaction related to an emergency proposal.
-		EnactProposal { dao_id: T::DaoId, proposal_hash: T::Hash, res: DispatchResultWithPostInfo },
+		EnactProposal { dao_id: T::DaoId, proposal_hash: H256, res: DispatchResultWithPostInfo },
 		/// Set the amount that needs to be staked for an emergency proposal.
 		SetPledge { dao_id: T::DaoId, amount: BalanceOf<T> },
 	}
@@ -156,11 +222,56 @@ pub mod pallet {
 		NotEmergencyMembers,
 		/// No permission to reject proposals.
 		PermissionDenied,
+		/// There are more emergency members than `MaxMembers`.
+		TooManyMembers,
+		/// There can only be a maximum of `MaxProposals` emergency proposals at one time.
+		TooManyProposals,
+		/// The reason is longer than `MaxReasonLen`.
+		ReasonTooLong,
+		/// The preimage of the proposal is not noted.
+		PreimageMissing,
+		/// Integer computation overflow.
+		Overflow,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Enact the proposals whose track period ends at this block.
+		///
+		/// At most `MaxScheduledPerBlock` proposals are enacted in one block, and no more than
+		/// `MaxScheduledWeight`. A proposal that is already enacted or rejected is skipped, and
+		/// one that doesn't fit is moved to the next block, unless it is heavier than
+		/// `MaxScheduledWeight` and is left to the extrinsic.
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let max_weight = T::MaxScheduledWeight::get();
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			let next = now.saturating_add(1u32.into());
+			for (dao_id, proposal_hash) in Agenda::<T>::take(now) {
+				weight.saturating_accrue(T::DbWeight::get().reads(2));
+				let call_weight = ProposalOf::<T>::get(dao_id, proposal_hash)
+					.and_then(|p| T::Preimages::peek(&p.call).ok())
+					.map(|(call, _)| call.get_dispatch_info().weight)
+					.unwrap_or_default();
+				let task_weight =
+					<T as pallet::Config>::WeightInfo::enact_proposal().saturating_add(call_weight);
+				if weight.saturating_add(task_weight).any_gt(max_weight) {
+					if !task_weight.any_gt(max_weight) {
+						weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+						Self::schedule(next, dao_id, proposal_hash);
+					}
+					continue
+				}
+				weight.saturating_accrue(task_weight);
+				let _ = with_storage_layer(|| Self::do_enact_proposal(dao_id, proposal_hash));
+			}
+			weight
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Set members who can make emergency proposals.
+		#[pallet::call_index(0)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_members())]
 		pub fn set_members(
 			origin: OriginFor<T>,
@@ -168,12 +279,15 @@ pub mod pallet {
 			members: Vec<T::AccountId>,
 		) -> DispatchResultWithPostInfo {
 			dao::Pallet::<T>::ensrue_dao_root(origin, dao_id)?;
-			Members::<T>::insert(dao_id, &members);
+			let bounded_members: BoundedVec<T::AccountId, T::MaxMembers> =
+				members.clone().try_into().map_err(|_| Error::<T>::TooManyMembers)?;
+			Members::<T>::insert(dao_id, bounded_members);
 			Self::deposit_event(Event::SetMembers { dao_id, members });
 			Ok(().into())
 		}
 
 		/// Set the amount that needs to be pledge for an emergency proposal.
+		#[pallet::call_index(1)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_pledge())]
 		pub fn set_pledge(
 			origin: OriginFor<T>,
@@ -188,40 +302,47 @@ pub mod pallet {
 		}
 
 		/// Externally initiated an emergency proposal.
+		///
+		/// The DAO account of the parent DAO is also an external origin.
+		#[pallet::call_index(2)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::external_track())]
 		#[transactional]
 		pub fn external_track(
 			origin: OriginFor<T>,
 			dao_id: T::DaoId,
-			proposal: Box<<T as dao::Config>::Call>,
+			proposal: BoundedCallOf<T>,
 			reason: Vec<u8>,
 		) -> DispatchResultWithPostInfo {
-			T::ExternalOrigin::ensure_origin(origin)?;
-			Self::try_propose(dao_id, *proposal, None, reason)
+			if T::ExternalOrigin::try_origin(origin.clone()).is_err() {
+				dao::Pallet::<T>::ensure_parent_root(origin, dao_id)?;
+			}
+			Self::try_propose(dao_id, proposal, None, reason)
 		}
 
 		/// Member initiates an urgent proposal.
+		#[pallet::call_index(3)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::internal_track())]
 		#[transactional]
 		pub fn internal_track(
 			origin: OriginFor<T>,
 			dao_id: T::DaoId,
-			proposal: Box<<T as dao::Config>::Call>,
+			proposal: BoundedCallOf<T>,
 			reason: Vec<u8>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 
 			ensure!(Members::<T>::get(dao_id).contains(&who), Error::<T>::NotEmergencyMembers);
-			Self::try_propose(dao_id, *proposal, Some(who), reason)
+			Self::try_propose(dao_id, proposal, Some(who), reason)
 		}
 
 		/// Rejected an emergency proposal.
+		#[pallet::call_index(4)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::reject())]
 		#[transactional]
 		pub fn reject(
 			origin: OriginFor<T>,
 			dao_id: T::DaoId,
-			proposal_hash: T::Hash,
+			proposal_hash: H256,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 
@@ -239,32 +360,59 @@ pub mod pallet {
 
 				ensure!(Self::now() < proposal.end_block, Error::<T>::ProposalEnded);
 				if let Some(who) = proposal.who.clone() {
-					T::Currency::slash_reserved(&who, proposal.pledge);
+					<T as pallet::Config>::Currency::slash_reserved(&who, proposal.pledge);
 				}
+				T::Preimages::drop(&proposal.call);
 				Self::deposit_event(Event::Rejected { dao_id, proposal_hash });
 				Ok(().into())
 			})
 		}
 
 		/// Execute a transaction related to an emergency proposal.
+		#[pallet::call_index(5)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::enact_proposal())]
 		#[transactional]
 		pub fn enact_proposal(
 			origin: OriginFor<T>,
 			dao_id: T::DaoId,
-			proposal_hash: T::Hash,
+			proposal_hash: H256,
 		) -> DispatchResultWithPostInfo {
 			let _ = ensure_signed(origin)?;
+			Self::do_enact_proposal(dao_id, proposal_hash)
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn now() -> BlockNumberFor<T> {
+			frame_system::Pallet::<T>::block_number()
+		}
+
+		/// Schedule the proposal to be enacted at `when`, or at the first later block whose
+		/// agenda is not full.
+		pub(crate) fn schedule(mut when: BlockNumberFor<T>, dao_id: T::DaoId, proposal_hash: H256) {
+			while Agenda::<T>::try_append(when, (dao_id, proposal_hash)).is_err() {
+				when = when.saturating_add(1u32.into());
+			}
+		}
+
+		/// Execute the transaction of the proposal if its track period has ended.
+		fn do_enact_proposal(dao_id: T::DaoId, proposal_hash: H256) -> DispatchResultWithPostInfo {
 			HashesOf::<T>::try_mutate(dao_id, |hashes| -> DispatchResultWithPostInfo {
+				// The record of an enacted proposal is kept, but it can't be enacted again.
+				ensure!(hashes.contains(&proposal_hash), Error::<T>::ProposalNotExists);
 				hashes.retain(|h| h != &proposal_hash);
 				let proposal = ProposalOf::<T>::take(dao_id, proposal_hash)
 					.ok_or(Error::<T>::ProposalNotExists)?;
 				ensure!(Self::now() >= proposal.end_block, Error::<T>::ProposalNotEnd);
+				let (call, _) = T::Preimages::peek(&proposal.call)
+					.map_err(|_| Error::<T>::PreimageMissing)?;
+				Self::ensure_proposal_allowed(dao_id, &call)?;
 				if let Some(who) = proposal.who.clone() {
-					T::Currency::unreserve(&who, proposal.pledge);
+					<T as pallet::Config>::Currency::unreserve(&who, proposal.pledge);
 				}
-				ProposalOf::<T>::insert(dao_id, proposal_hash, proposal.clone());
-				let res = proposal.call.dispatch_bypass_filter(
+				T::Preimages::drop(&proposal.call);
+				ProposalOf::<T>::insert(dao_id, proposal_hash, proposal);
+				let res = call.dispatch_bypass_filter(
 					frame_system::RawOrigin::Signed(dao::Pallet::<T>::try_get_dao_account_id(
 						dao_id,
 					)?)
@@ -275,28 +423,32 @@ pub mod pallet {
 				Ok(().into())
 			})
 		}
-	}
-
-	impl<T: Config> Pallet<T> {
-		// fixme
-		fn now() -> u32 {
 
-			// frame_system::Pallet::<T>::current_block_number()
-			100
+		/// Whether the DAO allows the call, checked again at enactment.
+		fn ensure_proposal_allowed(dao_id: T::DaoId, call: &CallOf<T>) -> DispatchResult {
+			dao::Pallet::<T>::ensure_call_allowed(dao_id, call)?;
+			dao::Pallet::<T>::ensure_can_dispatch(dao_id, call)?;
+			Ok(())
 		}
 
+		/// The call can be proposed by its hash before its preimage is noted.
 		fn try_propose(
 			dao_id: T::DaoId,
-			proposal: <T as dao::Config>::Call,
+			proposal: BoundedCallOf<T>,
 			who: Option<T::AccountId>,
 			reason: Vec<u8>,
 		) -> DispatchResultWithPostInfo {
-			let proposal_hash: T::Hash = T::Hashing::hash_of(&proposal);
+			if let Ok((call, _)) = T::Preimages::peek(&proposal) {
+				Self::ensure_proposal_allowed(dao_id, &call)?;
+			}
+			let reason: BoundedVec<u8, T::MaxReasonLen> =
+				reason.try_into().map_err(|_| Error::<T>::ReasonTooLong)?;
+			let proposal_hash = proposal.hash();
 			HashesOf::<T>::try_mutate(dao_id, |hashes| -> DispatchResultWithPostInfo {
 				if !hashes.contains(&proposal_hash) {
-					hashes.push(proposal_hash);
+					hashes.try_push(proposal_hash).map_err(|_| Error::<T>::TooManyProposals)?;
 					let end_block = Self::now()
-						.checked_add(T::TrackPeriod::get())
+						.checked_add(&T::TrackPeriod::get())
 						.ok_or(Error::<T>::StorageOverflow)?;
 					let pledge = if who.is_none() {
 						0u32.into()
@@ -304,8 +456,10 @@ pub mod pallet {
 						T::MinPledge::get().max(PledgeOf::<T>::get(dao_id))
 					};
 					if let Some(w) = who.clone() {
-						T::Currency::reserve(&w, pledge)?;
+						<T as pallet::Config>::Currency::reserve(&w, pledge)?;
 					}
+					Self::schedule(end_block, dao_id, proposal_hash);
+					T::Preimages::hold(&proposal);
 					ProposalOf::<T>::insert(
 						dao_id,
 						proposal_hash,
@@ -325,3 +479,38 @@ pub mod pallet {
 		}
 	}
 }
+
+impl<T: Config> AfterDissolve<T::DaoId> for Pallet<T> {
+	fn do_something(dao_id: T::DaoId) {
+		for proposal_hash in HashesOf::<T>::take(dao_id) {
+			if let Some(ProposalInfo { who, call, pledge, .. }) =
+				ProposalOf::<T>::take(dao_id, proposal_hash)
+			{
+				if let Some(who) = who {
+					<T as pallet::Config>::Currency::unreserve(&who, pledge);
+				}
+				T::Preimages::drop(&call);
+			}
+		}
+		let _ = ProposalOf::<T>::clear_prefix(dao_id, u32::MAX, None);
+	}
+}
+
+impl<T: Config> ApplyTemplate<T::AccountId, T::DaoId, DaoTemplateOf<T>> for Pallet<T> {
+	fn apply_template(
+		_creator: &T::AccountId,
+		dao_id: T::DaoId,
+		template: &DaoTemplateOf<T>,
+	) -> DispatchResult {
+		if let Some(pledge) = template.emergency_pledge {
+			// The template is in the currency of `dao`, which may not fit the one of this module.
+			let amount = TryInto::<u128>::try_into(pledge)
+				.ok()
+				.and_then(|pledge| BalanceOf::<T>::try_from(pledge).ok())
+				.ok_or(Error::<T>::Overflow)?;
+			ensure!(amount >= T::MinPledge::get(), Error::<T>::PledgeTooLow);
+			PledgeOf::<T>::insert(dao_id, amount);
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,291 @@
+use super::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Defensive, DefensiveTruncateFrom, OnRuntimeUpgrade, StorageVersion},
+};
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+
+/// Store the end block of the proposals as `BlockNumberFor<T>` instead of `u32`.
+pub mod v1 {
+	use super::*;
+
+	pub type OldProposalInfoOf<T> = ProposalInfo<
+		<T as frame_system::Config>::AccountId,
+		<T as dao::Config>::Call,
+		BalanceOf<T>,
+		u32,
+		BoundedVec<u8, <T as Config>::MaxReasonLen>,
+	>;
+
+	/// `HashesOf` as it is keyed before the version 2.
+	#[frame_support::storage_alias]
+	pub type HashesOf<T: Config> = StorageMap<
+		Pallet<T>,
+		Identity,
+		<T as dao::Config>::DaoId,
+		Vec<<T as frame_system::Config>::Hash>,
+		ValueQuery,
+	>;
+
+	/// `ProposalOf` as it is keyed before the version 2.
+	#[frame_support::storage_alias]
+	pub type ProposalOf<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Identity,
+		<T as dao::Config>::DaoId,
+		Identity,
+		<T as frame_system::Config>::Hash,
+		v2::InlineProposalInfoOf<T>,
+		OptionQuery,
+	>;
+
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 0 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			ProposalOf::<T>::translate::<OldProposalInfoOf<T>, _>(|_, _, old| {
+				translated += 1;
+				Some(ProposalInfo {
+					who: old.who,
+					end_block: old.end_block.into(),
+					call: old.call,
+					pledge: old.pledge,
+					reason: old.reason,
+				})
+			});
+			StorageVersion::new(1).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			Ok((ProposalOf::<T>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let count = u32::decode(&mut &state[..]).map_err(|_| "The state is not a count.")?;
+			ensure!(
+				ProposalOf::<T>::iter().count() as u32 == count,
+				"Some proposals are not migrated."
+			);
+			ensure!(StorageVersion::get::<Pallet<T>>() == 1, "The storage version is not 1.");
+			Ok(())
+		}
+	}
+}
+
+/// Key `HashesOf` and `ProposalOf` with hashers that are safe for the proposal hashes.
+pub mod v2 {
+	use super::*;
+
+	/// The proposal information before the version 3, with the call stored inline.
+	pub type InlineProposalInfoOf<T> = ProposalInfo<
+		<T as frame_system::Config>::AccountId,
+		<T as dao::Config>::Call,
+		BalanceOf<T>,
+		BlockNumberFor<T>,
+		BoundedVec<u8, <T as Config>::MaxReasonLen>,
+	>;
+
+	/// `HashesOf` as it is before the version 3.
+	#[frame_support::storage_alias]
+	pub type HashesOf<T: Config> = StorageMap<
+		Pallet<T>,
+		Twox64Concat,
+		<T as dao::Config>::DaoId,
+		BoundedVec<<T as frame_system::Config>::Hash, <T as Config>::MaxProposals>,
+		ValueQuery,
+	>;
+
+	/// `ProposalOf` as it is before the version 3.
+	#[frame_support::storage_alias]
+	pub type ProposalOf<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Twox64Concat,
+		<T as dao::Config>::DaoId,
+		Blake2_128Concat,
+		<T as frame_system::Config>::Hash,
+		InlineProposalInfoOf<T>,
+		OptionQuery,
+	>;
+
+	pub struct MigrateToV2<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 1 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let hashes = v1::HashesOf::<T>::drain().collect::<Vec<_>>();
+			let proposals = v1::ProposalOf::<T>::drain().collect::<Vec<_>>();
+			let rekeyed = (hashes.len() + proposals.len()) as u64;
+			for (dao_id, hashes) in hashes {
+				HashesOf::<T>::insert(dao_id, BoundedVec::defensive_truncate_from(hashes));
+			}
+			for (dao_id, hash, proposal) in proposals {
+				ProposalOf::<T>::insert(dao_id, hash, proposal);
+			}
+			StorageVersion::new(2).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(rekeyed + 1, rekeyed * 2 + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let daos = v1::HashesOf::<T>::iter_keys().count() as u32;
+			let proposals = v1::ProposalOf::<T>::iter_keys().count() as u32;
+			Ok((daos, proposals).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let (daos, proposals) = <(u32, u32)>::decode(&mut &state[..])
+				.map_err(|_| "The state is not the counts.")?;
+			ensure!(HashesOf::<T>::iter().count() as u32 == daos, "Some hashes are lost.");
+			ensure!(
+				ProposalOf::<T>::iter().count() as u32 == proposals,
+				"Some proposals are lost."
+			);
+			ensure!(StorageVersion::get::<Pallet<T>>() == 2, "The storage version is not 2.");
+			Ok(())
+		}
+	}
+}
+
+/// Store the calls of the open proposals in the preimages, the enacted ones keep only their
+/// hashes.
+///
+/// The proposals keep their hashes, which assumes that the runtime hashes with `BlakeTwo256`.
+pub mod v3 {
+	use super::*;
+
+	pub struct MigrateToV3<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 2 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			// Only the calls of the open proposals are stored and requested, the enacted ones are
+			// kept as records of their hashes.
+			let hashes = v2::HashesOf::<T>::iter_values().flatten().collect::<Vec<_>>();
+			ProposalOf::<T>::translate::<v2::InlineProposalInfoOf<T>, _>(|dao_id, hash, old| {
+				translated += 1;
+				if !hashes.iter().any(|h| h.as_ref() == hash.as_bytes()) {
+					let len = old.call.encoded_size() as u32;
+					return Some(ProposalInfo {
+						who: old.who,
+						end_block: old.end_block,
+						call: Bounded::Lookup { hash, len },
+						pledge: old.pledge,
+						reason: old.reason,
+					})
+				}
+				let call = match T::Preimages::bound(old.call).defensive() {
+					Ok(call) => call,
+					Err(_) => {
+						frame_support::log::warn!(
+							"The emergency proposal {:?} of the DAO {:?} is too long and is dropped.",
+							hash,
+							dao_id
+						);
+						// The pledge is returned, and the proposal can't be enacted.
+						if let Some(who) = old.who {
+							<T as pallet::Config>::Currency::unreserve(&who, old.pledge);
+						}
+						HashesOf::<T>::mutate(dao_id, |hashes| hashes.retain(|h| h != &hash));
+						return None
+					},
+				};
+				T::Preimages::hold(&call);
+				Some(ProposalInfo {
+					who: old.who,
+					end_block: old.end_block,
+					call,
+					pledge: old.pledge,
+					reason: old.reason,
+				})
+			});
+			StorageVersion::new(3).put::<Pallet<T>>();
+			// Each open call is also noted and requested in the preimages.
+			T::DbWeight::get().reads_writes(translated * 3 + 1, translated * 3 + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			Ok((v2::ProposalOf::<T>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let count = u32::decode(&mut &state[..]).map_err(|_| "The state is not a count.")?;
+			ensure!(
+				ProposalOf::<T>::iter().count() as u32 == count,
+				"Some proposals are not migrated."
+			);
+			ensure!(StorageVersion::get::<Pallet<T>>() == 3, "The storage version is not 3.");
+			Ok(())
+		}
+	}
+}
+
+/// Schedule the open proposals that exist before they are enacted automatically.
+pub mod v4 {
+	use super::*;
+	use sp_runtime::traits::Saturating;
+
+	fn open<T: Config>() -> Vec<(T::DaoId, H256, BlockNumberFor<T>)> {
+		HashesOf::<T>::iter()
+			.flat_map(|(dao_id, hashes)| {
+				hashes.into_iter().filter_map(move |hash| {
+					ProposalOf::<T>::get(dao_id, hash).map(|p| (dao_id, hash, p.end_block))
+				})
+			})
+			.collect()
+	}
+
+	pub struct MigrateToV4<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 3 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let next = frame_system::Pallet::<T>::block_number().saturating_add(1u32.into());
+			let proposals = open::<T>();
+			let scheduled = proposals.len() as u64;
+			for (dao_id, hash, end_block) in proposals {
+				Pallet::<T>::schedule(end_block.max(next), dao_id, hash);
+			}
+			StorageVersion::new(4).put::<Pallet<T>>();
+			let daos = HashesOf::<T>::iter_keys().count() as u64;
+			T::DbWeight::get().reads_writes(daos + scheduled * 2 + 1, scheduled + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			Ok((open::<T>().len() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let count = u32::decode(&mut &state[..]).map_err(|_| "The state is not a count.")?;
+			ensure!(
+				Agenda::<T>::iter_values().map(|tasks| tasks.len() as u32).sum::<u32>() >= count,
+				"Some proposals are not scheduled."
+			);
+			ensure!(StorageVersion::get::<Pallet<T>>() == 4, "The storage version is not 4.");
+			Ok(())
+		}
+	}
+}
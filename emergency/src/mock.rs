@@ -7,9 +7,15 @@ use frame_support::{
 	debug, parameter_types,
 	sp_tracing::debug,
 	traits::{ConstU16, ConstU32, ConstU64, Contains},
+	weights::Weight,
 };
 use frame_system::{self, Account, EnsureRoot};
-use primitives::{ids::Nft, traits::BaseCallFilter, types::MemberCount};
+use primitives::{
+	ids::Nft,
+	mock::Collections,
+	traits::BaseCallFilter,
+	types::{CallId, MemberCount},
+};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -30,6 +36,7 @@ frame_support::construct_runtime!(
 
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Preimage: pallet_preimage::{Pallet, Call, Storage, Event<T>},
 		Emergency: emergency::{ Pallet, Call, Event<T>, Storage },
 		DAO: dao::{ Pallet, Call, Event<T>, Storage },
 		Sudo: sudo::{ Pallet, Call, Event<T>, Storage },
@@ -63,14 +70,7 @@ impl frame_system::Config for Test {
 	type MaxConsumers = ConstU32<16>;
 }
 
-impl TryFrom<Call> for u64 {
-	type Error = ();
-	fn try_from(call: Call) -> Result<Self, Self::Error> {
-		match call {
-			_ => Ok(0u64),
-		}
-	}
-}
+primitives::impl_call_id!(Call);
 impl BaseCallFilter<Call> for Nft<u64> {
 	fn contains(&self, call: Call) -> bool {
 		true
@@ -89,13 +89,31 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = ();
 }
 
+impl pallet_preimage::Config for Test {
+	type Event = Event;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<u64>;
+	type BaseDeposit = ConstU64<2>;
+	type ByteDeposit = ConstU64<1>;
+}
+
 impl dao::Config for Test {
 	type Event = Event;
 	type Call = Call;
-	type CallId = u64;
+	type CallId = CallId;
 	type DaoId = u64;
 	type ConcreteId = Nft<u64>;
+	type Inspector = Collections;
 	type AfterCreate = ();
+	type AfterDissolve = (Emergency, Sudo);
+	type AfterCreatorChanged = ();
+	type ApplyTemplate = (Emergency, Sudo);
+	type MaxTemplateOrigins = ConstU32<10>;
+	type Currency = Balances;
+	type CreationDeposit = ConstU64<0>;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type StringLimit = ConstU32<50>;
 	type WeightInfo = ();
 }
 
@@ -107,14 +125,21 @@ impl sudo::Config for Test {
 parameter_types! {
 	pub const MinPledge: u64 = 100u64;
 	pub const TrackPeriod: u64 = 100u64;
+	pub MaxScheduledWeight: Weight = Weight::from_all(1_000_000_000);
 }
 
 impl emergency::Config for Test {
 	type Event = Event;
 	type ExternalOrigin = EnsureRoot<u64>;
 	type Currency = Balances;
+	type Preimages = Preimage;
 	type MinPledge = MinPledge;
 	type TrackPeriod = TrackPeriod;
+	type MaxMembers = ConstU32<10>;
+	type MaxProposals = ConstU32<10>;
+	type MaxReasonLen = ConstU32<10>;
+	type MaxScheduledPerBlock = ConstU32<10>;
+	type MaxScheduledWeight = MaxScheduledWeight;
 	type WeightInfo = ();
 }
 
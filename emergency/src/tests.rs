@@ -2,15 +2,34 @@
 
 use super::*;
 use crate::mock::{Call, Origin, *};
-use frame_support::assert_ok;
+use frame_support::{
+	assert_noop, assert_ok,
+	storage::unhashed,
+	traits::{Hooks, OnRuntimeUpgrade, StorageVersion},
+};
 use primitives::ids::Nft;
-use sp_runtime::traits::BlakeTwo256;
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	BuildStorage,
+};
 
 pub const ALICE: u64 = 1;
 pub const BOB: u64 = 2;
 
 pub fn create_dao() {
-	dao::Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 4]).unwrap();
+	dao::Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 4], None).unwrap();
+}
+
+pub fn bounded(call: Call) -> BoundedCallOf<Test> {
+	Preimage::bound(call).unwrap()
+}
+
+pub fn run_to_block(n: u64) {
+	while frame_system::Pallet::<Test>::block_number() < n {
+		let next = frame_system::Pallet::<Test>::block_number() + 1;
+		frame_system::Pallet::<Test>::set_block_number(next);
+		crate::Pallet::<Test>::on_initialize(next);
+	}
 }
 
 fn rec_balance() {
@@ -48,7 +67,7 @@ fn external() {
 	assert_ok!(crate::Pallet::<Test>::external_track(
 		Origin::root(),
 		0u64,
-		Box::new(proposal),
+		bounded(proposal),
 		vec![1, 2, 3, 4]
 	));
 	assert!(crate::HashesOf::<Test>::get(0u64).len() > 0);
@@ -61,13 +80,13 @@ fn internal() {
 	assert_ok!(crate::Pallet::<Test>::internal_track(
 		Origin::signed(ALICE),
 		0u64,
-		Box::new(proposal.clone()),
+		bounded(proposal.clone()),
 		vec![1, 2, 3, 4],
 	));
 	assert!(crate::Pallet::<Test>::internal_track(
 		Origin::signed(ALICE),
 		0u64,
-		Box::new(proposal),
+		bounded(proposal),
 		vec![1, 2, 3, 4]
 	)
 	.is_err());
@@ -100,6 +119,33 @@ fn internal_track_should_work() {
 	});
 }
 
+#[test]
+fn limits_should_work() {
+	new_test_ext().execute_with(|| {
+		create_dao();
+		rec_balance();
+		let dao_account = dao::Daos::<Test>::get(0u64).unwrap().dao_account_id;
+		assert_noop!(
+			crate::Pallet::<Test>::set_members(
+				Origin::signed(dao_account),
+				0u64,
+				(0..11u64).collect()
+			),
+			crate::Error::<Test>::TooManyMembers
+		);
+		let proposal = Call::decode(&mut &get_proposal()[..]).unwrap();
+		assert_noop!(
+			crate::Pallet::<Test>::external_track(
+				Origin::root(),
+				0u64,
+				bounded(proposal),
+				vec![1; 11]
+			),
+			crate::Error::<Test>::ReasonTooLong
+		);
+	});
+}
+
 #[test]
 fn reject_internal_track() {
 	new_test_ext().execute_with(|| {
@@ -177,3 +223,237 @@ fn enact_proposal_should_work() {
 		assert_eq!(crate::PledgeOf::<Test>::get(0u64), 1000);
 	});
 }
+
+#[test]
+fn matured_proposal_should_be_enacted_automatically() {
+	new_test_ext().execute_with(|| {
+		internal();
+		let proposal_hash = BlakeTwo256::hash(&get_proposal()[..]);
+		assert_eq!(crate::Agenda::<Test>::get(TrackPeriod::get()), vec![(0u64, proposal_hash)]);
+		run_to_block(TrackPeriod::get() - 1);
+		assert_ne!(crate::PledgeOf::<Test>::get(0u64), 1000);
+		run_to_block(TrackPeriod::get());
+		assert_eq!(crate::PledgeOf::<Test>::get(0u64), 1000);
+		assert!(crate::HashesOf::<Test>::get(0u64).is_empty());
+		assert!(crate::Agenda::<Test>::get(TrackPeriod::get()).is_empty());
+		assert_noop!(
+			crate::Pallet::<Test>::enact_proposal(Origin::signed(BOB), 0u64, proposal_hash),
+			crate::Error::<Test>::ProposalNotExists
+		);
+	});
+}
+
+#[test]
+fn proposal_should_be_moved_when_the_agenda_is_full() {
+	new_test_ext().execute_with(|| {
+		let end = TrackPeriod::get();
+		let full = (0..10u8).map(|i| (1u64, H256::repeat_byte(i))).collect::<Vec<_>>();
+		crate::Agenda::<Test>::insert(end, BoundedVec::truncate_from(full));
+		internal();
+		let proposal_hash = BlakeTwo256::hash(&get_proposal()[..]);
+		assert_eq!(crate::Agenda::<Test>::get(end + 1), vec![(0u64, proposal_hash)]);
+		run_to_block(end + 1);
+		assert_eq!(crate::PledgeOf::<Test>::get(0u64), 1000);
+		assert!(crate::HashesOf::<Test>::get(0u64).is_empty());
+	});
+}
+
+#[test]
+fn parent_external_track_should_work() {
+	new_test_ext().execute_with(|| {
+		create_dao();
+		dao::Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(1u64), vec![1; 4], None)
+			.unwrap();
+		let parent_account = dao::Daos::<Test>::get(0u64).unwrap().dao_account_id;
+		let child_account = dao::Daos::<Test>::get(1u64).unwrap().dao_account_id;
+		let proposal = Call::Emergency(crate::Call::set_pledge { dao_id: 1u64, amount: 1000u64 });
+
+		assert!(crate::Pallet::<Test>::external_track(
+			Origin::signed(parent_account),
+			1u64,
+			bounded(proposal.clone()),
+			vec![1, 2, 3, 4]
+		)
+		.is_err());
+		assert_ok!(dao::Pallet::<Test>::set_parent(
+			Origin::signed(child_account),
+			1u64,
+			Some(0u64)
+		));
+		assert_ok!(dao::Pallet::<Test>::accept_parent(Origin::signed(parent_account), 1u64));
+		assert_ok!(crate::Pallet::<Test>::external_track(
+			Origin::signed(parent_account),
+			1u64,
+			bounded(proposal),
+			vec![1, 2, 3, 4]
+		));
+		assert!(crate::HashesOf::<Test>::get(1u64).len() > 0);
+	});
+}
+
+#[test]
+fn genesis_config_should_work() {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	crate::GenesisConfig::<Test> {
+		members: vec![(0u64, vec![ALICE, BOB])],
+		pledges: vec![(0u64, 1000u64)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		assert_eq!(crate::Members::<Test>::get(0u64), vec![ALICE, BOB]);
+		assert_eq!(crate::PledgeOf::<Test>::get(0u64), 1000u64);
+	});
+}
+
+#[test]
+fn migrate_to_v1_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<Pallet<Test>>();
+		let hash = BlakeTwo256::hash(&get_proposal()[..]);
+		unhashed::put(
+			&migrations::v1::ProposalOf::<Test>::hashed_key_for(0u64, hash),
+			&ProposalInfo {
+				who: Some(ALICE),
+				end_block: 100u32,
+				call: Call::decode(&mut &get_proposal()[..]).unwrap(),
+				pledge: 1000u64,
+				reason: vec![1, 2, 3, 4],
+			},
+		);
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<Pallet<Test>>(), 1);
+		let proposal = migrations::v1::ProposalOf::<Test>::get(0u64, hash).unwrap();
+		assert_eq!(proposal.end_block, 100u64);
+		assert_eq!(proposal.who, Some(ALICE));
+		assert_eq!(proposal.pledge, 1000u64);
+	});
+}
+
+#[test]
+fn migrate_to_v2_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<Pallet<Test>>();
+		let hash = BlakeTwo256::hash(&get_proposal()[..]);
+		migrations::v1::HashesOf::<Test>::insert(0u64, vec![hash]);
+		migrations::v1::ProposalOf::<Test>::insert(
+			0u64,
+			hash,
+			ProposalInfo {
+				who: Some(ALICE),
+				end_block: 100u64,
+				call: Call::decode(&mut &get_proposal()[..]).unwrap(),
+				pledge: 1000u64,
+				reason: vec![1, 2, 3, 4].try_into().unwrap(),
+			},
+		);
+		assert!(migrations::v2::ProposalOf::<Test>::get(0u64, hash).is_none());
+
+		migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<Pallet<Test>>(), 2);
+		assert_eq!(migrations::v2::HashesOf::<Test>::get(0u64), vec![hash]);
+		let proposal = migrations::v2::ProposalOf::<Test>::get(0u64, hash).unwrap();
+		assert_eq!((proposal.end_block, proposal.who), (100u64, Some(ALICE)));
+		assert!(!migrations::v1::ProposalOf::<Test>::contains_key(0u64, hash));
+	});
+}
+
+#[test]
+fn migrate_to_v3_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(2).put::<Pallet<Test>>();
+		let hash = BlakeTwo256::hash(&get_proposal()[..]);
+		migrations::v2::HashesOf::<Test>::insert(0u64, BoundedVec::truncate_from(vec![hash]));
+		migrations::v2::ProposalOf::<Test>::insert(
+			0u64,
+			hash,
+			ProposalInfo {
+				who: Some(ALICE),
+				end_block: 100u64,
+				call: Call::decode(&mut &get_proposal()[..]).unwrap(),
+				pledge: 1000u64,
+				reason: vec![1, 2, 3, 4].try_into().unwrap(),
+			},
+		);
+		// An enacted proposal is not in `HashesOf`.
+		let enacted = H256::repeat_byte(1);
+		migrations::v2::ProposalOf::<Test>::insert(
+			0u64,
+			enacted,
+			ProposalInfo {
+				who: Some(ALICE),
+				end_block: 50u64,
+				call: Call::decode(&mut &get_proposal()[..]).unwrap(),
+				pledge: 1000u64,
+				reason: vec![1, 2, 3, 4].try_into().unwrap(),
+			},
+		);
+
+		migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<Pallet<Test>>(), 3);
+		assert_eq!(HashesOf::<Test>::get(0u64), vec![hash]);
+		let proposal = ProposalOf::<Test>::get(0u64, hash).unwrap();
+		assert_eq!(proposal.call.hash(), hash);
+		assert_eq!(Preimage::peek(&proposal.call).unwrap().0.encode(), get_proposal());
+		assert!(Preimage::is_requested(&hash));
+		assert_eq!(
+			ProposalOf::<Test>::get(0u64, enacted).unwrap().call,
+			Bounded::Lookup { hash: enacted, len: get_proposal().len() as u32 }
+		);
+		assert!(Preimage::len(&enacted).is_none());
+	});
+}
+
+#[test]
+fn proposal_should_wait_for_preimage() {
+	new_test_ext().execute_with(|| {
+		set_members();
+		rec_balance();
+		let hash = BlakeTwo256::hash(&get_proposal()[..]);
+		assert_ok!(crate::Pallet::<Test>::internal_track(
+			Origin::signed(ALICE),
+			0u64,
+			Bounded::Lookup { hash, len: get_proposal().len() as u32 },
+			vec![1, 2, 3, 4],
+		));
+		assert!(Preimage::is_requested(&hash));
+
+		frame_system::Pallet::<Test>::set_block_number(10000);
+		assert_noop!(
+			crate::Pallet::<Test>::enact_proposal(Origin::signed(BOB), 0u64, hash),
+			crate::Error::<Test>::PreimageMissing
+		);
+		assert_ok!(Preimage::note_preimage(Origin::signed(BOB), get_proposal()));
+		assert_ok!(crate::Pallet::<Test>::enact_proposal(Origin::signed(BOB), 0u64, hash));
+		assert_eq!(crate::PledgeOf::<Test>::get(0u64), 1000);
+		assert!(!Preimage::is_requested(&hash));
+	});
+}
+
+#[test]
+fn migrate_to_v4_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(3).put::<Pallet<Test>>();
+		frame_system::Pallet::<Test>::set_block_number(50);
+		let call = Preimage::bound(Call::decode(&mut &get_proposal()[..]).unwrap()).unwrap();
+		let proposal = |end_block: u64| ProposalInfo {
+			who: Some(ALICE),
+			end_block,
+			call: call.clone(),
+			pledge: 1000u64,
+			reason: vec![1, 2, 3, 4].try_into().unwrap(),
+		};
+		let (open, late, enacted) = (H256::repeat_byte(1), H256::repeat_byte(2), call.hash());
+		HashesOf::<Test>::insert(0u64, BoundedVec::truncate_from(vec![open, late]));
+		ProposalOf::<Test>::insert(0u64, open, proposal(100u64));
+		ProposalOf::<Test>::insert(0u64, late, proposal(10u64));
+		ProposalOf::<Test>::insert(0u64, enacted, proposal(100u64));
+
+		migrations::v4::MigrateToV4::<Test>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<Pallet<Test>>(), 4);
+		assert_eq!(Agenda::<Test>::get(100u64), vec![(0u64, open)]);
+		// A proposal that is already due is enacted in the next block.
+		assert_eq!(Agenda::<Test>::get(51u64), vec![(0u64, late)]);
+		assert_eq!(Agenda::<Test>::iter().count(), 2);
+	});
+}
@@ -52,6 +52,7 @@ pub struct DaosWeight<T>(PhantomData<T>);
         }
             // Storage: DaoEmergency HashesOf (r:1 w:1)
             // Storage: DaoEmergency ProposalOf (r:0 w:1)
+            // Storage: Preimage StatusFor (r:1 w:1)
         fn external_track() -> Weight {
             Weight::from_all(2000_0000)
         }
@@ -60,6 +61,7 @@ pub struct DaosWeight<T>(PhantomData<T>);
             // Storage: DaoEmergency HashesOf (r:1 w:1)
             // Storage: DaoEmergency PledgeOf (r:1 w:0)
             // Storage: DaoEmergency ProposalOf (r:0 w:1)
+            // Storage: Preimage StatusFor (r:1 w:1)
         fn internal_track() -> Weight {
             Weight::from_all(2000_0000)
         }
@@ -67,6 +69,7 @@ pub struct DaosWeight<T>(PhantomData<T>);
             // Storage: Currencies UsersNumber (r:1 w:1)
             // Storage: DaoEmergency HashesOf (r:1 w:1)
             // Storage: DaoEmergency ProposalOf (r:1 w:1)
+            // Storage: Preimage StatusFor (r:1 w:1)
         fn reject() -> Weight {
             Weight::from_all(2000_0000)
         }
@@ -75,6 +78,8 @@ pub struct DaosWeight<T>(PhantomData<T>);
             // Storage: DaoEmergency HashesOf (r:1 w:1)
             // Storage: DaoEmergency ProposalOf (r:1 w:1)
             // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: Preimage StatusFor (r:1 w:1)
+            // Storage: Preimage PreimageFor (r:1 w:0)
         fn enact_proposal() -> Weight {
             Weight::from_all(2000_0000)
         }
@@ -94,6 +99,7 @@ pub struct DaosWeight<T>(PhantomData<T>);
         }
             // Storage: DaoEmergency HashesOf (r:1 w:1)
             // Storage: DaoEmergency ProposalOf (r:0 w:1)
+            // Storage: Preimage StatusFor (r:1 w:1)
         fn external_track() -> Weight {
             Weight::from_all(2000_0000)
         }
@@ -102,6 +108,7 @@ pub struct DaosWeight<T>(PhantomData<T>);
             // Storage: DaoEmergency HashesOf (r:1 w:1)
             // Storage: DaoEmergency PledgeOf (r:1 w:0)
             // Storage: DaoEmergency ProposalOf (r:0 w:1)
+            // Storage: Preimage StatusFor (r:1 w:1)
         fn internal_track() -> Weight {
             Weight::from_all(2000_0000)
         }
@@ -109,6 +116,7 @@ pub struct DaosWeight<T>(PhantomData<T>);
             // Storage: Currencies UsersNumber (r:1 w:1)
             // Storage: DaoEmergency HashesOf (r:1 w:1)
             // Storage: DaoEmergency ProposalOf (r:1 w:1)
+            // Storage: Preimage StatusFor (r:1 w:1)
         fn reject() -> Weight {
             Weight::from_all(2000_0000)
         }
@@ -117,6 +125,8 @@ pub struct DaosWeight<T>(PhantomData<T>);
             // Storage: DaoEmergency HashesOf (r:1 w:1)
             // Storage: DaoEmergency ProposalOf (r:1 w:1)
             // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: Preimage StatusFor (r:1 w:1)
+            // Storage: Preimage PreimageFor (r:1 w:0)
         fn enact_proposal() -> Weight {
             Weight::from_all(2000_0000)
         }
@@ -1,8 +1,43 @@
 use super::*;
 pub use codec::MaxEncodedLen;
-use sp_runtime::traits::{CheckedAdd, One};
+use sp_runtime::traits::{BlakeTwo256, CheckedAdd, Hash, One};
 use sp_std::ops::{Add, Mul};
 
+/// Sub-accounts replace the last byte of the prefix with `/` and put the id before the index,
+/// so that they differ from the main account.
+fn sub_prefix(prefix: &[u8; 4]) -> [u8; 4] {
+	[prefix[0], prefix[1], prefix[2], b'/']
+}
+
+/// If the prefix, the id and the index don't fit in the account id, the account is derived from
+/// their hash instead, so that the sub-accounts of different ids don't collide. Such an account
+/// can't be converted back.
+fn into_sub_account<T: Encode + Decode, Id: Encode>(prefix: &[u8; 4], id: &Id, index: u32) -> T {
+	let data = (sub_prefix(prefix), id, index).encode();
+	let account = T::decode(&mut TrailingZeroInput(&data)).unwrap();
+	if account.encoded_size() >= data.len() {
+		account
+	} else {
+		BlakeTwo256::hash(&data).using_encoded(|b| T::decode(&mut TrailingZeroInput(b))).unwrap()
+	}
+}
+
+fn try_from_sub_account<T: Encode, Id: Decode>(prefix: &[u8; 4], x: &T) -> Option<(Id, u32)> {
+	x.using_encoded(|d| {
+		if d[0..4] != sub_prefix(prefix) {
+			return None
+		}
+		let mut cursor = &d[4..];
+		let result = Decode::decode(&mut cursor).ok()?;
+		let index = Decode::decode(&mut cursor).ok()?;
+		if cursor.iter().all(|x| *x == 0) {
+			Some((result, index))
+		} else {
+			None
+		}
+	})
+}
+
 #[derive(Decode, Encode, Copy, Clone, Default, Debug, TypeInfo, MaxEncodedLen, Eq, PartialEq)]
 pub struct DaoId(pub u64);
 
@@ -46,12 +81,15 @@ impl CheckedAdd for DaoId {
 	}
 }
 #[derive(Decode, Encode, Copy, Clone, Default, Debug, TypeInfo, MaxEncodedLen, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Nft<ClassId>(pub ClassId);
 
 #[derive(Decode, Encode, Copy, Clone, Default, Debug, TypeInfo, MaxEncodedLen, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Fungible<TokenId>(pub TokenId);
 
 #[derive(Decode, Encode, Copy, Clone, Default, Debug, TypeInfo, MaxEncodedLen, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct RoomId<Id>(pub Id);
 
 impl<T: Encode + Decode, ClassId: Encode + Decode> AccountIdConversion<T> for Nft<ClassId> {
@@ -73,6 +111,14 @@ impl<T: Encode + Decode, ClassId: Encode + Decode> AccountIdConversion<T> for Nf
 			}
 		})
 	}
+
+	fn into_sub_account(&self, index: u32) -> T {
+		into_sub_account(b"nft ", self, index)
+	}
+
+	fn try_from_sub_account(x: &T) -> Option<(Self, u32)> {
+		try_from_sub_account(b"nft ", x)
+	}
 }
 
 impl<T: Encode + Decode, TokenId: Encode + Decode> AccountIdConversion<T> for Fungible<TokenId> {
@@ -94,6 +140,14 @@ impl<T: Encode + Decode, TokenId: Encode + Decode> AccountIdConversion<T> for Fu
 			}
 		})
 	}
+
+	fn into_sub_account(&self, index: u32) -> T {
+		into_sub_account(b"fung", self, index)
+	}
+
+	fn try_from_sub_account(x: &T) -> Option<(Self, u32)> {
+		try_from_sub_account(b"fung", x)
+	}
 }
 
 impl<T: Encode + Decode, Id: Encode + Decode> AccountIdConversion<T> for RoomId<Id> {
@@ -115,4 +169,12 @@ impl<T: Encode + Decode, Id: Encode + Decode> AccountIdConversion<T> for RoomId<
 			}
 		})
 	}
+
+	fn into_sub_account(&self, index: u32) -> T {
+		into_sub_account(b"room", self, index)
+	}
+
+	fn try_from_sub_account(x: &T) -> Option<(Self, u32)> {
+		try_from_sub_account(b"room", x)
+	}
 }
@@ -19,10 +19,14 @@
 pub use codec::{MaxEncodedLen, Decode, Encode};
 // pub use frame_support::codec::{Decode, Encode};
 pub use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+pub use serde::{Deserialize, Serialize};
 pub use sp_runtime::RuntimeDebug;
 pub use sp_std::{prelude::*, result, vec};
 pub mod constant;
 pub mod ids;
+#[cfg(feature = "test-helpers")]
+pub mod mock;
 pub mod traits;
 pub mod types;
 
@@ -33,6 +37,12 @@ pub trait AccountIdConversion<AccountId>: Sized {
 
 	/// Try to convert an account ID into this type. Might not succeed.
 	fn try_from_account(a: &AccountId) -> Option<Self>;
+
+	/// Convert into the `index`th sub-account ID, such as a treasury or escrow account.
+	fn into_sub_account(&self, index: u32) -> AccountId;
+
+	/// Try to convert a sub-account ID into this type and its index. Might not succeed.
+	fn try_from_sub_account(a: &AccountId) -> Option<(Self, u32)>;
 }
 
 pub struct TrailingZeroInput<'a>(pub &'a [u8]);
@@ -0,0 +1,20 @@
+//! Test helpers shared by the mock runtimes of the modules.
+
+use frame_support::traits::tokens::nonfungibles;
+
+/// The nft collections of the mock runtimes, whose owners can create their DAOs.
+pub struct Collections;
+
+impl nonfungibles::Inspect<u64> for Collections {
+	type ItemId = u64;
+	type CollectionId = u64;
+
+	fn owner(_collection: &u64, _item: &u64) -> Option<u64> {
+		None
+	}
+
+	/// Collection 3 belongs to account 3, the others belong to account 1.
+	fn collection_owner(collection: &u64) -> Option<u64> {
+		Some(if *collection == 3 { 3 } else { 1 })
+	}
+}
@@ -1,5 +1,6 @@
 use super::*;
-use sp_runtime::DispatchError;
+use frame_support::traits::tokens::{fungibles, nonfungibles};
+use sp_runtime::{DispatchError, DispatchResult};
 
 pub struct BadOrigin;
 
@@ -29,7 +30,44 @@ impl<A: Clone, B: Clone> AfterCreate<A, B> for () {
 	fn do_something(_a: A, _b: B) {}
 }
 
-pub trait TryCreate<AccountId: Clone + Ord, DaoId: Clone, DispatchError> {
+pub trait AfterDissolve<DaoId> {
+	fn do_something(dao_id: DaoId);
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(6)]
+impl<DaoId: Clone> AfterDissolve<DaoId> for Tuple {
+	fn do_something(dao_id: DaoId) {
+		for_tuples!( #( Tuple::do_something(dao_id.clone()); )* );
+	}
+}
+
+pub trait AfterCreatorChanged<AccountId, DaoId> {
+	fn do_something(dao_id: DaoId, old: &AccountId, new: &AccountId);
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(6)]
+impl<AccountId, DaoId: Clone> AfterCreatorChanged<AccountId, DaoId> for Tuple {
+	fn do_something(dao_id: DaoId, old: &AccountId, new: &AccountId) {
+		for_tuples!( #( Tuple::do_something(dao_id.clone(), old, new); )* );
+	}
+}
+
+pub trait ApplyTemplate<AccountId, DaoId, Template> {
+	fn apply_template(creator: &AccountId, dao_id: DaoId, template: &Template) -> DispatchResult;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(6)]
+impl<AccountId, DaoId: Clone, Template> ApplyTemplate<AccountId, DaoId, Template> for Tuple {
+	fn apply_template(creator: &AccountId, dao_id: DaoId, template: &Template) -> DispatchResult {
+		for_tuples!( #( Tuple::apply_template(creator, dao_id.clone(), template)?; )* );
+		Ok(())
+	}
+}
+
+/// Check whether `who` can create a DAO for the specific group.
+///
+/// `Inspector` is the module that knows who owns the group, such as an nft or assets module.
+pub trait TryCreate<AccountId: Clone + Ord, DaoId: Clone, DispatchError, Inspector = ()> {
 	fn try_create(&self, who: AccountId, dao_id: DaoId) -> result::Result<(), DispatchError>;
 }
 
@@ -73,14 +111,30 @@ impl<OuterOrigin: Clone, Argument: Clone> EnsureOriginWithArg<OuterOrigin, Argum
 	}
 }
 
-impl<AccountId: Clone + Ord, DaoId: Default + Clone, Id: Clone>
-	TryCreate<AccountId, DaoId, DispatchError> for ids::Nft<Id>
+/// Only the owner of the nft class can create its DAO.
+impl<AccountId: Clone + Ord, DaoId: Clone, ClassId, Inspector>
+	TryCreate<AccountId, DaoId, DispatchError, Inspector> for ids::Nft<ClassId>
+where
+	Inspector: nonfungibles::Inspect<AccountId, CollectionId = ClassId>,
+{
+	fn try_create(&self, who: AccountId, _dao_id: DaoId) -> Result<(), DispatchError> {
+		match Inspector::collection_owner(&self.0) {
+			Some(owner) if owner == who => Ok(()),
+			_ => Err(DispatchError::BadOrigin),
+		}
+	}
+}
+
+/// Only the owner of the asset can create its DAO.
+impl<AccountId: Clone + Ord, DaoId: Clone, TokenId: Clone, Inspector>
+	TryCreate<AccountId, DaoId, DispatchError, Inspector> for ids::Fungible<TokenId>
+where
+	Inspector: fungibles::roles::Inspect<AccountId, AssetId = TokenId>,
 {
-	fn try_create(&self, _who: AccountId, _dao_id: DaoId) -> Result<(), DispatchError> {
-		if !cfg!(any(feature = "std", feature = "runtime-benchmarks", test)) {
-			todo!()
-		} else {
-			Ok(())
+	fn try_create(&self, who: AccountId, _dao_id: DaoId) -> Result<(), DispatchError> {
+		match Inspector::owner(self.0.clone()) {
+			Some(owner) if owner == who => Ok(()),
+			_ => Err(DispatchError::BadOrigin),
 		}
 	}
 }
@@ -12,6 +12,7 @@ pub type MemberCount = u32;
 pub type RealCallId = u32;
 
 #[derive(PartialEq, Encode, Decode, RuntimeDebug, Clone, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Proportion<MemberCount> {
 	MoreThan(MemberCount, MemberCount),
 	AtLeast(MemberCount, MemberCount),
@@ -25,6 +26,7 @@ impl Default for Proportion<MemberCount> {
 
 #[cfg_attr(any(feature = "std", test), derive(Debug))]
 #[derive(PartialEq, Encode, Decode, Clone, TypeInfo, Copy, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum DoAsEnsureOrigin<Pro, C> {
 	Proportion(Pro),
 	Member,
@@ -37,3 +39,61 @@ impl<Pro: Default, C: Default> Default for DoAsEnsureOrigin<Pro, C> {
 		Self::Root
 	}
 }
+
+/// The id of a call, made of the index of its pallet in the runtime and the index of the call
+/// in that pallet.
+///
+/// Both indexes are the first two bytes of the encoded runtime call, so the id stays stable as
+/// long as `#[pallet::index]` and `#[pallet::call_index]` do not change.
+#[derive(
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Encode,
+	Decode,
+	RuntimeDebug,
+	Clone,
+	Copy,
+	Default,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CallId {
+	pub pallet_index: u8,
+	pub call_index: u8,
+}
+
+impl CallId {
+	pub fn new(pallet_index: u8, call_index: u8) -> Self {
+		Self { pallet_index, call_index }
+	}
+
+	/// Read the id from the encoded runtime call.
+	pub fn from_call<Call: Encode>(call: &Call) -> Option<Self> {
+		call.using_encoded(|bytes| match bytes {
+			[pallet_index, call_index, ..] => Some(Self::new(*pallet_index, *call_index)),
+			_ => None,
+		})
+	}
+}
+
+/// Implement `TryFrom<Call>` for [`CallId`], so that the runtime can use it as the `CallId` of
+/// the DAO pallets.
+///
+/// ```ignore
+/// daos_primitives::impl_call_id!(RuntimeCall);
+/// ```
+#[macro_export]
+macro_rules! impl_call_id {
+	($call:ty) => {
+		impl ::core::convert::TryFrom<$call> for $crate::types::CallId {
+			type Error = ();
+
+			fn try_from(call: $call) -> ::core::result::Result<Self, Self::Error> {
+				Self::from_call(&call).ok_or(())
+			}
+		}
+	};
+}
@@ -0,0 +1,279 @@
+// Copyright 2022 daos-org.
+// This file is part of DAOS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Daos RPC
+//!
+//! RPC methods of the [`DaosApi`](daos_runtime_api::DaosApi) runtime api.
+//!
+//! Every method takes an optional block hash and queries the best block by default.
+
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
+use codec::Codec;
+pub use daos_runtime_api::DaosApi as DaosRuntimeApi;
+use daos_runtime_api::{
+	DaoInfo, DoAsEnsureOrigin, MemberCount, Opinion, PropIndex, Proportion, ReferendumIndex,
+	Status, Tally, VoteInfo,
+};
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use std::{marker::PhantomData, sync::Arc};
+
+#[rpc(client, server)]
+pub trait DaosApi<
+	BlockHash,
+	AccountId,
+	DaoId,
+	ConcreteId,
+	BlockNumber,
+	Balance,
+	CallId,
+	Hash,
+	Pledge,
+>
+{
+	/// All DAOs created by the account.
+	#[method(name = "daos_daosOf")]
+	fn daos_of(&self, creator: AccountId, at: Option<BlockHash>) -> RpcResult<Vec<DaoId>>;
+
+	/// The information of the DAO.
+	#[method(name = "daos_daoInfo")]
+	fn dao_info(
+		&self,
+		dao_id: DaoId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<DaoInfo<AccountId, BlockNumber, ConcreteId, Status, Balance, DaoId>>>;
+
+	/// The DAO account of the DAO.
+	#[method(name = "daos_daoAccount")]
+	fn dao_account(&self, dao_id: DaoId, at: Option<BlockHash>) -> RpcResult<Option<AccountId>>;
+
+	/// The open public proposals in `square`.
+	#[method(name = "daos_squareProposals")]
+	fn square_proposals(
+		&self,
+		dao_id: DaoId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(PropIndex, Hash, AccountId)>>;
+
+	/// The open proposals in `agency`.
+	#[method(name = "daos_agencyProposals")]
+	fn agency_proposals(&self, dao_id: DaoId, at: Option<BlockHash>) -> RpcResult<Vec<Hash>>;
+
+	/// The open proposals in `emergency`.
+	#[method(name = "daos_emergencyProposals")]
+	fn emergency_proposals(&self, dao_id: DaoId, at: Option<BlockHash>) -> RpcResult<Vec<Hash>>;
+
+	/// The tally of the ongoing referendum in `square`.
+	#[method(name = "daos_referendumTally")]
+	fn referendum_tally(
+		&self,
+		dao_id: DaoId,
+		index: ReferendumIndex,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Tally<Balance>>>;
+
+	/// The origin that `agency` requires for the call.
+	#[method(name = "daos_requiredOrigin")]
+	fn required_origin(
+		&self,
+		dao_id: DaoId,
+		call_id: CallId,
+		at: Option<BlockHash>,
+	) -> RpcResult<DoAsEnsureOrigin<Proportion<MemberCount>, MemberCount>>;
+
+	/// The votes of the account in the DAO.
+	#[method(name = "daos_votesOf")]
+	fn votes_of(
+		&self,
+		who: AccountId,
+		dao_id: DaoId,
+		at: Option<BlockHash>,
+	) -> RpcResult<
+		Vec<
+			VoteInfo<
+				DaoId,
+				ConcreteId,
+				Pledge,
+				BlockNumber,
+				Balance,
+				Opinion<Balance>,
+				ReferendumIndex,
+			>,
+		>,
+	>;
+}
+
+/// Error code of the failed runtime api call.
+const RUNTIME_ERROR: i32 = 1;
+
+fn runtime_error(e: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Unable to query the DAOs.",
+		Some(format!("{:?}", e)),
+	))
+	.into()
+}
+
+/// Provides the RPC methods to query the DAOs.
+pub struct Daos<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Daos<C, Block> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, DaoId, ConcreteId, BlockNumber, Balance, CallId, Hash, Pledge>
+	DaosApiServer<
+		<Block as BlockT>::Hash,
+		AccountId,
+		DaoId,
+		ConcreteId,
+		BlockNumber,
+		Balance,
+		CallId,
+		Hash,
+		Pledge,
+	> for Daos<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: DaosRuntimeApi<
+		Block,
+		AccountId,
+		DaoId,
+		ConcreteId,
+		BlockNumber,
+		Balance,
+		CallId,
+		Hash,
+		Pledge,
+	>,
+	AccountId: Codec,
+	DaoId: Codec,
+	ConcreteId: Codec,
+	BlockNumber: Codec,
+	Balance: Codec,
+	CallId: Codec,
+	Hash: Codec,
+	Pledge: Codec,
+{
+	fn daos_of(
+		&self,
+		creator: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<DaoId>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().daos_of(at, creator).map_err(runtime_error)
+	}
+
+	fn dao_info(
+		&self,
+		dao_id: DaoId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<DaoInfo<AccountId, BlockNumber, ConcreteId, Status, Balance, DaoId>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().dao_info(at, dao_id).map_err(runtime_error)
+	}
+
+	fn dao_account(
+		&self,
+		dao_id: DaoId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<AccountId>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().dao_account(at, dao_id).map_err(runtime_error)
+	}
+
+	fn square_proposals(
+		&self,
+		dao_id: DaoId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(PropIndex, Hash, AccountId)>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().square_proposals(at, dao_id).map_err(runtime_error)
+	}
+
+	fn agency_proposals(
+		&self,
+		dao_id: DaoId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<Hash>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().agency_proposals(at, dao_id).map_err(runtime_error)
+	}
+
+	fn emergency_proposals(
+		&self,
+		dao_id: DaoId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<Hash>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().emergency_proposals(at, dao_id).map_err(runtime_error)
+	}
+
+	fn referendum_tally(
+		&self,
+		dao_id: DaoId,
+		index: ReferendumIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Tally<Balance>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().referendum_tally(at, dao_id, index).map_err(runtime_error)
+	}
+
+	fn required_origin(
+		&self,
+		dao_id: DaoId,
+		call_id: CallId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<DoAsEnsureOrigin<Proportion<MemberCount>, MemberCount>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().required_origin(at, dao_id, call_id).map_err(runtime_error)
+	}
+
+	fn votes_of(
+		&self,
+		who: AccountId,
+		dao_id: DaoId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<
+		Vec<
+			VoteInfo<
+				DaoId,
+				ConcreteId,
+				Pledge,
+				BlockNumber,
+				Balance,
+				Opinion<Balance>,
+				ReferendumIndex,
+			>,
+		>,
+	> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().votes_of(at, who, dao_id).map_err(runtime_error)
+	}
+}
@@ -0,0 +1,116 @@
+// Copyright 2022 daos-org.
+// This file is part of DAOS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Daos Runtime API
+//!
+//! Query the DAOs without decoding the raw storage of each module.
+//!
+//! The runtime implements it with the query functions of the modules, for example:
+//! ```ignore
+//! impl daos_runtime_api::DaosApi<Block, AccountId, DaoId, ConcreteId, BlockNumber, Balance, CallId, Hash, Pledge> for Runtime {
+//! 	fn daos_of(creator: AccountId) -> Vec<DaoId> {
+//! 		CreateDao::daos_of(&creator)
+//! 	}
+//! 	fn dao_info(dao_id: DaoId) -> Option<DaoInfo<AccountId, BlockNumber, ConcreteId, Status, Balance, DaoId>> {
+//! 		CreateDao::daos(dao_id)
+//! 	}
+//! 	fn dao_account(dao_id: DaoId) -> Option<AccountId> {
+//! 		CreateDao::try_get_dao_account_id(dao_id).ok()
+//! 	}
+//! 	fn square_proposals(dao_id: DaoId) -> Vec<(PropIndex, Hash, AccountId)> {
+//! 		Square::open_proposals(dao_id)
+//! 	}
+//! 	fn agency_proposals(dao_id: DaoId) -> Vec<Hash> {
+//! 		Agency::proposals(dao_id).into_inner()
+//! 	}
+//! 	fn emergency_proposals(dao_id: DaoId) -> Vec<Hash> {
+//! 		Emergency::hashes_of(dao_id).into_inner()
+//! 	}
+//! 	fn referendum_tally(dao_id: DaoId, index: ReferendumIndex) -> Option<Tally<Balance>> {
+//! 		Square::referendum_tally(dao_id, index)
+//! 	}
+//! 	fn required_origin(dao_id: DaoId, call_id: CallId) -> DoAsEnsureOrigin<Proportion<MemberCount>, MemberCount> {
+//! 		Agency::ensures(dao_id, call_id)
+//! 	}
+//! 	fn votes_of(who: AccountId, dao_id: DaoId) -> Vec<VoteInfo<DaoId, ConcreteId, Pledge, BlockNumber, Balance, Opinion<Balance>, ReferendumIndex>> {
+//! 		Square::votes_in(&who, dao_id)
+//! 	}
+//! }
+//! ```
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+
+use codec::Codec;
+pub use dao::{DaoInfo, Status};
+pub use primitives::types::{DoAsEnsureOrigin, MemberCount, Proportion};
+use sp_std::vec::Vec;
+pub use square::{Opinion, PropIndex, ReferendumIndex, Tally, VoteInfo};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+sp_api::decl_runtime_apis! {
+	/// The API to query the DAOs.
+	pub trait DaosApi<AccountId, DaoId, ConcreteId, BlockNumber, Balance, CallId, Hash, Pledge>
+	where
+		AccountId: Codec,
+		DaoId: Codec,
+		ConcreteId: Codec,
+		BlockNumber: Codec,
+		Balance: Codec,
+		CallId: Codec,
+		Hash: Codec,
+		Pledge: Codec,
+	{
+		/// All DAOs created by the account.
+		fn daos_of(creator: AccountId) -> Vec<DaoId>;
+
+		/// The information of the DAO.
+		fn dao_info(
+			dao_id: DaoId,
+		) -> Option<DaoInfo<AccountId, BlockNumber, ConcreteId, Status, Balance, DaoId>>;
+
+		/// The DAO account of the DAO.
+		fn dao_account(dao_id: DaoId) -> Option<AccountId>;
+
+		/// The open public proposals in `square`.
+		fn square_proposals(dao_id: DaoId) -> Vec<(PropIndex, Hash, AccountId)>;
+
+		/// The open proposals in `agency`.
+		fn agency_proposals(dao_id: DaoId) -> Vec<Hash>;
+
+		/// The open proposals in `emergency`.
+		fn emergency_proposals(dao_id: DaoId) -> Vec<Hash>;
+
+		/// The tally of the ongoing referendum in `square`.
+		fn referendum_tally(dao_id: DaoId, index: ReferendumIndex) -> Option<Tally<Balance>>;
+
+		/// The origin that `agency` requires for the call.
+		fn required_origin(
+			dao_id: DaoId,
+			call_id: CallId,
+		) -> DoAsEnsureOrigin<Proportion<MemberCount>, MemberCount>;
+
+		/// The votes of the account in the DAO.
+		fn votes_of(
+			who: AccountId,
+			dao_id: DaoId,
+		) -> Vec<VoteInfo<DaoId, ConcreteId, Pledge, BlockNumber, Balance, Opinion<Balance>, ReferendumIndex>>;
+	}
+}
@@ -0,0 +1,257 @@
+#![allow(dead_code)]
+use crate::{
+	DaoInfo, DaosApi, DoAsEnsureOrigin, MemberCount, Opinion, PropIndex, Proportion,
+	ReferendumIndex, Status, Tally, VoteInfo,
+};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64, Everything},
+	weights::Weight,
+	RuntimeDebug,
+};
+use frame_system::EnsureRoot;
+use primitives::{ids::Nft, mock::Collections, traits::BaseCallFilter, types::CallId};
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, Block as BlockT, IdentityLookup},
+	BuildStorage, DispatchError,
+};
+use sp_std::vec::Vec;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+pub type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the runtime api.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Preimage: pallet_preimage::{Pallet, Call, Storage, Event<T>},
+		DAO: dao::{ Pallet, Call, Event<T>, Storage },
+		Square: square::{ Pallet, Call, Event<T>, Storage },
+		Agency: agency::{ Pallet, Call, Event<T>, Storage, Origin<T> },
+		Emergency: emergency::{ Pallet, Call, Event<T>, Storage },
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+primitives::impl_call_id!(Call);
+impl BaseCallFilter<Call> for Nft<u64> {
+	fn contains(&self, _call: Call) -> bool {
+		true
+	}
+}
+
+impl pallet_balances::Config for Test {
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
+impl pallet_preimage::Config for Test {
+	type Event = Event;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<u64>;
+	type BaseDeposit = ConstU64<2>;
+	type ByteDeposit = ConstU64<1>;
+}
+
+impl dao::Config for Test {
+	type Event = Event;
+	type Call = Call;
+	type CallId = CallId;
+	type DaoId = u64;
+	type ConcreteId = Nft<u64>;
+	type Inspector = Collections;
+	type AfterCreate = ();
+	type AfterDissolve = (Square, Agency, Emergency);
+	type AfterCreatorChanged = ();
+	type ApplyTemplate = (Square, Agency, Emergency);
+	type MaxTemplateOrigins = ConstU32<10>;
+	type Currency = Balances;
+	type CreationDeposit = ConstU64<0>;
+	type ForceOrigin = EnsureRoot<u64>;
+	type StringLimit = ConstU32<50>;
+	type WeightInfo = ();
+}
+
+#[derive(
+	PartialEq, Eq, Encode, Decode, RuntimeDebug, Clone, TypeInfo, Copy, MaxEncodedLen, Default,
+)]
+pub struct Vote(pub u64);
+
+impl square::Pledge<u64, u64, u64, (), u64, DispatchError> for Vote {
+	fn try_vote(
+		&self,
+		_who: &u64,
+		_dao_id: &u64,
+		_conviction: &(),
+	) -> Result<(u64, u64), DispatchError> {
+		Ok((self.0, 100u64))
+	}
+
+	fn vote_end_do(&self, _who: &u64, _dao_id: &u64) -> Result<(), DispatchError> {
+		Ok(())
+	}
+
+	fn electorate(_dao_id: &u64) -> u64 {
+		1000u64
+	}
+}
+
+parameter_types! {
+	pub const MinPledge: u64 = 100u64;
+	pub const TrackPeriod: u64 = 100u64;
+	pub MaxScheduledWeight: Weight = Weight::from_all(1_000_000_000);
+}
+
+impl square::Config for Test {
+	type Event = Event;
+	type Pledge = Vote;
+	type Conviction = ();
+	type Currency = Balances;
+	type Preimages = Preimage;
+	type MaxProposals = ConstU32<100>;
+	type MaxDeposits = ConstU32<3>;
+	type MaxReserves = ConstU32<100>;
+	type MaxVotes = ConstU32<100>;
+	type MaxScheduledPerBlock = ConstU32<10>;
+	type MaxScheduledWeight = MaxScheduledWeight;
+	type WeightInfo = ();
+}
+
+impl agency::Config for Test {
+	type Event = Event;
+	type Origin = Origin;
+	type Proposal = Call;
+	type CollectiveBaseCallFilter = Everything;
+	type DefaultVote = agency::PrimeDefaultVote;
+	type MaxMembersForSystem = ConstU32<4>;
+	type MaxProposalsForSystem = ConstU32<100>;
+	type MaxScheduledPerBlock = ConstU32<10>;
+	type MaxScheduledWeight = MaxScheduledWeight;
+	type WeightInfo = ();
+}
+
+impl emergency::Config for Test {
+	type Event = Event;
+	type ExternalOrigin = EnsureRoot<u64>;
+	type Currency = Balances;
+	type Preimages = Preimage;
+	type MinPledge = MinPledge;
+	type TrackPeriod = TrackPeriod;
+	type MaxMembers = ConstU32<10>;
+	type MaxProposals = ConstU32<10>;
+	type MaxReasonLen = ConstU32<10>;
+	type MaxScheduledPerBlock = ConstU32<10>;
+	type MaxScheduledWeight = MaxScheduledWeight;
+	type WeightInfo = ();
+}
+
+sp_api::impl_runtime_apis! {
+	impl sp_api::Core<Block> for Test {
+		fn version() -> sp_api::RuntimeVersion {
+			unimplemented!()
+		}
+
+		fn execute_block(_block: Block) {
+			unimplemented!()
+		}
+
+		fn initialize_block(_header: &<Block as BlockT>::Header) {
+			unimplemented!()
+		}
+	}
+
+	impl DaosApi<Block, u64, u64, Nft<u64>, u64, u64, CallId, H256, Vote> for Test {
+		fn daos_of(creator: u64) -> Vec<u64> {
+			DAO::daos_of(&creator)
+		}
+
+		fn dao_info(dao_id: u64) -> Option<DaoInfo<u64, u64, Nft<u64>, Status, u64, u64>> {
+			DAO::daos(dao_id)
+		}
+
+		fn dao_account(dao_id: u64) -> Option<u64> {
+			DAO::try_get_dao_account_id(dao_id).ok()
+		}
+
+		fn square_proposals(dao_id: u64) -> Vec<(PropIndex, H256, u64)> {
+			Square::open_proposals(dao_id)
+		}
+
+		fn agency_proposals(dao_id: u64) -> Vec<H256> {
+			Agency::proposals(dao_id).into_inner()
+		}
+
+		fn emergency_proposals(dao_id: u64) -> Vec<H256> {
+			Emergency::hashes_of(dao_id).into_inner()
+		}
+
+		fn referendum_tally(dao_id: u64, index: ReferendumIndex) -> Option<Tally<u64>> {
+			Square::referendum_tally(dao_id, index)
+		}
+
+		fn required_origin(
+			dao_id: u64,
+			call_id: CallId,
+		) -> DoAsEnsureOrigin<Proportion<MemberCount>, MemberCount> {
+			Agency::ensures(dao_id, call_id)
+		}
+
+		fn votes_of(
+			who: u64,
+			dao_id: u64,
+		) -> Vec<VoteInfo<u64, Nft<u64>, Vote, u64, u64, Opinion<u64>, ReferendumIndex>> {
+			Square::votes_in(&who, dao_id)
+		}
+	}
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 10), (2, 10), (3, 10)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	t.into()
+}
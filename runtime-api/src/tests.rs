@@ -0,0 +1,70 @@
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_ok, traits::StorePreimage, BoundedVec};
+use primitives::{ids::Nft, types::CallId, AccountIdConversion};
+use sp_core::H256;
+
+pub const ALICE: u64 = 1;
+
+/// Call the runtime api as the runtime implements it.
+macro_rules! api {
+	($method:ident($($arg:expr),*)) => {
+		<Test as runtime_decl_for_daos_api::DaosApiV1<
+			Block,
+			u64,
+			u64,
+			Nft<u64>,
+			u64,
+			u64,
+			CallId,
+			H256,
+			Vote,
+		>>::$method($($arg),*)
+	};
+}
+
+#[test]
+pub fn daos_api_should_work() {
+	new_test_ext().execute_with(|| {
+		assert!(api!(daos_of(ALICE)).is_empty());
+		assert_eq!(api!(dao_info(0u64)), None);
+		assert_eq!(api!(dao_account(0u64)), None);
+
+		assert_ok!(DAO::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 4], None));
+		assert_eq!(api!(daos_of(ALICE)), vec![0u64]);
+		assert_eq!(api!(dao_info(0u64)).map(|dao| dao.concrete_id), Some(Nft(0u64)));
+		assert_eq!(api!(dao_account(0u64)), Some(Nft(0u64).into_account()));
+
+		let proposal =
+			Preimage::bound(Call::System(frame_system::Call::remark { remark: vec![1; 4] }))
+				.unwrap();
+		assert_ok!(Square::propose(Origin::signed(ALICE), 0u64, proposal.clone(), 0u64));
+		assert_eq!(api!(square_proposals(0u64)), vec![(0u32, proposal.hash(), ALICE)]);
+
+		assert_eq!(api!(referendum_tally(0u64, 0u32)), None);
+		frame_system::Pallet::<Test>::set_block_number(10000);
+		assert_ok!(Square::open_table(Origin::signed(ALICE), 0u64));
+		assert!(api!(square_proposals(0u64)).is_empty());
+		assert_ok!(Square::vote_for_referendum(
+			Origin::signed(ALICE),
+			0u64,
+			0u32,
+			Vote(100u64),
+			(),
+			Opinion::AYES,
+		));
+		assert_eq!(api!(referendum_tally(0u64, 0u32)).map(|tally| tally.ayes), Some(100u64));
+		assert_eq!(api!(votes_of(ALICE, 0u64)).len(), 1);
+		assert!(api!(votes_of(ALICE, 1u64)).is_empty());
+
+		let hash = H256::repeat_byte(1);
+		agency::Proposals::<Test>::insert(0u64, BoundedVec::truncate_from(vec![hash]));
+		assert_eq!(api!(agency_proposals(0u64)), vec![hash]);
+		emergency::HashesOf::<Test>::insert(0u64, BoundedVec::truncate_from(vec![hash]));
+		assert_eq!(api!(emergency_proposals(0u64)), vec![hash]);
+
+		assert_eq!(api!(required_origin(0u64, CallId::default())), DoAsEnsureOrigin::Root);
+		agency::EnsureOrigins::<Test>::insert(0u64, CallId::default(), DoAsEnsureOrigin::Member);
+		assert_eq!(api!(required_origin(0u64, CallId::default())), DoAsEnsureOrigin::Member);
+	});
+}
@@ -19,13 +19,19 @@ const LaunchTime: u32 = 1000000;
 
 fn get_alice<T: Config>() -> T::AccountId {
 	let alice = account("alice", 1, 1);
-	T::Currency::deposit_creating(&alice, (100000 * DOLLARS).saturated_into::<BalanceOf<T>>());
+	<T as Config>::Currency::deposit_creating(
+		&alice,
+		(100000 * DOLLARS).saturated_into::<BalanceOf<T>>(),
+	);
 	alice
 }
 
 fn get_dao_account<T: Config>(second_id: T::ConcreteId) -> T::AccountId {
 	let who = second_id.into_account();
-	T::Currency::deposit_creating(&who, (100000 * DOLLARS).saturated_into::<BalanceOf<T>>());
+	<T as Config>::Currency::deposit_creating(
+		&who,
+		(100000 * DOLLARS).saturated_into::<BalanceOf<T>>(),
+	);
 	who
 }
 
@@ -37,15 +43,16 @@ fn creat_dao<T: Config>() -> (T::DaoId, T::ConcreteId) {
 		SystemOrigin::Signed(alice).into(),
 		second_id,
 		vec![1; 4],
+		None,
 	)
 	.is_ok());
 	(dao_id, second_id)
 }
 
-fn get_call<T: Config>(dao_id: T::DaoId) -> (<T as dao::Config>::Call, T::Hash) {
-	let proposal: <T as dao::Config>::Call =
-		DaoCall::<T>::dao_remark { dao_id, remark: vec![1; 20] }.into();
-	(proposal.clone(), T::Hashing::hash_of(&proposal))
+fn get_call<T: Config>(dao_id: T::DaoId) -> (BoundedCallOf<T>, H256) {
+	let call: CallOf<T> = DaoCall::<T>::dao_remark { dao_id, remark: vec![1; 20] }.into();
+	let proposal = T::Preimages::bound(call).unwrap();
+	(proposal.clone(), proposal.hash())
 }
 
 fn create_proposal<T: Config>() -> (T::DaoId, T::ConcreteId, ProposalIndex) {
@@ -55,11 +62,11 @@ fn create_proposal<T: Config>() -> (T::DaoId, T::ConcreteId, ProposalIndex) {
 	assert!(Democracy::<T>::propose(
 		SystemOrigin::Signed(get_alice::<T>()).into(),
 		dao_id,
-		Box::new(proposal),
+		proposal,
 		amount
 	)
 	.is_ok());
-	frame_system::Pallet::<T>::set_block_number(T::BlockNumber::from(LaunchTime));
+	frame_system::Pallet::<T>::set_block_number(BlockNumberFor::<T>::from(LaunchTime));
 	(dao_id, second_id, 0 as ProposalIndex)
 }
 
@@ -87,7 +94,7 @@ fn vote1<T: Config>() -> (T::DaoId, T::AccountId, ProposalIndex) {
 
 fn enact<T: Config>() -> T::AccountId {
 	let (dao_id, dao_account, index) = vote1::<T>();
-	frame_system::Pallet::<T>::set_block_number(T::BlockNumber::from(2 as u32 * LaunchTime));
+	frame_system::Pallet::<T>::set_block_number(BlockNumberFor::<T>::from(2 as u32 * LaunchTime));
 	assert!(Democracy::<T>::enact_proposal(
 		SystemOrigin::Signed(dao_account.clone()).into(),
 		dao_id,
@@ -102,7 +109,7 @@ benchmarks! {
 		let (dao_id, second_id) = creat_dao::<T>();
 		let (proposal, _) = get_call::<T>(dao_id);
 		let amount = (1000 * DOLLARS).saturated_into::<BalanceOf<T>>();
-	}:_(SystemOrigin::Signed(get_alice::<T>()), dao_id, Box::new(proposal), amount)
+	}:_(SystemOrigin::Signed(get_alice::<T>()), dao_id, proposal, amount)
 
 	second {
 		let (dao_id, second_id, index) = create_proposal::<T>();
@@ -125,13 +132,29 @@ benchmarks! {
 
 	enact_proposal {
 		let (dao_id, dao_account, index) = vote1::<T>();
-		frame_system::Pallet::<T>::set_block_number(T::BlockNumber::from(2 as u32 * LaunchTime));
+		frame_system::Pallet::<T>::set_block_number(BlockNumberFor::<T>::from(2 as u32 * LaunchTime));
 	}:_(SystemOrigin::Signed(dao_account), dao_id, index)
 
 	unlock {
 		let account = enact::<T>();
 	}:_(SystemOrigin::Signed(account))
 
+	delegate {
+		let (dao_id, dao_account, index) = vote1::<T>();
+	}:_(SystemOrigin::Signed(get_alice::<T>()), dao_id, dao_account, T::Pledge::default(), T::Conviction::default())
+
+	undelegate {
+		let (dao_id, dao_account, index) = vote1::<T>();
+		assert!(Democracy::<T>::delegate(
+			SystemOrigin::Signed(get_alice::<T>()).into(),
+			dao_id,
+			dao_account,
+			T::Pledge::default(),
+			T::Conviction::default()
+		)
+		.is_ok());
+	}:_(SystemOrigin::Signed(get_alice::<T>()), dao_id)
+
 	set_min_vote_weight_for_every_call {
 		let (dao_id, second_id) = creat_dao::<T>();
 		let dao = get_dao_account::<T>(second_id);
@@ -145,7 +168,7 @@ benchmarks! {
 	set_launch_period {
 		let (dao_id, second_id) = creat_dao::<T>();
 		let dao = get_dao_account::<T>(second_id);
-	}:_(SystemOrigin::Signed(dao), dao_id, T::BlockNumber::from(100u32))
+	}:_(SystemOrigin::Signed(dao), dao_id, BlockNumberFor::<T>::from(100u32))
 
 	set_minimum_deposit {
 		let (dao_id, second_id) = creat_dao::<T>();
@@ -155,15 +178,40 @@ benchmarks! {
 	set_voting_period {
 		let (dao_id, second_id) = creat_dao::<T>();
 		let dao = get_dao_account::<T>(second_id);
-	}:_(SystemOrigin::Signed(dao), dao_id, T::BlockNumber::from(100u32))
+	}:_(SystemOrigin::Signed(dao), dao_id, BlockNumberFor::<T>::from(100u32))
 
 	set_rerserve_period {
 		let (dao_id, second_id) = creat_dao::<T>();
 		let dao = get_dao_account::<T>(second_id);
-	}:_(SystemOrigin::Signed(dao), dao_id, T::BlockNumber::from(100u32))
+	}:_(SystemOrigin::Signed(dao), dao_id, BlockNumberFor::<T>::from(100u32))
 
 	set_enactment_period {
 		let (dao_id, second_id) = creat_dao::<T>();
 		let dao = get_dao_account::<T>(second_id);
-	}:_(SystemOrigin::Signed(dao), dao_id, T::BlockNumber::from(100u32))
+	}:_(SystemOrigin::Signed(dao), dao_id, BlockNumberFor::<T>::from(100u32))
+
+	set_vote_locking_period {
+		let (dao_id, second_id) = creat_dao::<T>();
+		let dao = get_dao_account::<T>(second_id);
+	}:_(SystemOrigin::Signed(dao), dao_id, BlockNumberFor::<T>::from(100u32))
+
+	set_vote_threshold_for_every_call {
+		let (dao_id, second_id) = creat_dao::<T>();
+		let dao = get_dao_account::<T>(second_id);
+	}:_(SystemOrigin::Signed(dao), dao_id, T::CallId::default(), Some(VoteThreshold::SuperMajorityApprove))
+
+	set_referendum_threshold {
+		let (dao_id, second_id, index) = launch::<T>();
+		let dao = get_dao_account::<T>(second_id);
+	}:_(SystemOrigin::Signed(dao), dao_id, index, Some(VoteThreshold::SuperMajorityApprove))
+
+	withdraw_proposal {
+		let (dao_id, second_id, index) = create_proposal::<T>();
+	}:_(SystemOrigin::Signed(get_alice::<T>()), dao_id, index)
+
+	blacklist {
+		let (dao_id, second_id, index) = create_proposal::<T>();
+		let (_, hash) = get_call::<T>(dao_id);
+		let dao = get_dao_account::<T>(second_id);
+	}:_(SystemOrigin::Signed(dao), dao_id, hash)
 }
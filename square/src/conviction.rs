@@ -0,0 +1,203 @@
+// Copyright 2022 daos-org.
+// This file is part of DAOS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A reference `Pledge` that locks the native balance of the voter with a conviction.
+//!
+//! The vote weight is the locked amount multiplied by the conviction, and the lock lasts until the
+//! referendum ends, then for `VoteLockingPeriod` of the DAO multiplied by the lock periods of the
+//! conviction.
+//! All votes of an account share one lock, which is always as large as the largest vote that
+//! is still locked. The electorate of every DAO is the total issuance.
+
+use super::*;
+use frame_support::{
+	ensure,
+	traits::{LockIdentifier, LockableCurrency, WithdrawReasons},
+	CloneNoBound, DefaultNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+use sp_runtime::traits::Bounded;
+use sp_std::marker::PhantomData;
+
+/// The id of the lock on the balance of the voters.
+pub const CONVICTION_ID: LockIdentifier = *b"daos/cvt";
+
+/// How much the vote is magnified and how long the balance is locked.
+#[derive(
+	Encode,
+	Decode,
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	Default,
+	TypeInfo,
+	MaxEncodedLen,
+	Debug,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Conviction {
+	/// 0.1x votes, unlocked once the referendum ends.
+	#[default]
+	None,
+	/// 1x votes, locked for one period.
+	Locked1x,
+	/// 2x votes, locked for 2x periods.
+	Locked2x,
+	/// 3x votes, locked for 4x periods.
+	Locked3x,
+	/// 4x votes, locked for 8x periods.
+	Locked4x,
+	/// 5x votes, locked for 16x periods.
+	Locked5x,
+	/// 6x votes, locked for 32x periods.
+	Locked6x,
+}
+
+impl Conviction {
+	/// The number of `VoteLockingPeriod`s that the balance is locked for.
+	pub fn lock_periods(self) -> u32 {
+		match self {
+			Conviction::None => 0,
+			Conviction::Locked1x => 1,
+			Conviction::Locked2x => 2,
+			Conviction::Locked3x => 4,
+			Conviction::Locked4x => 8,
+			Conviction::Locked5x => 16,
+			Conviction::Locked6x => 32,
+		}
+	}
+
+	/// The vote weight of the `capital` locked with this conviction.
+	pub fn votes<B: From<u8> + Zero + Copy + CheckedMul + CheckedDiv + Bounded>(
+		self,
+		capital: B,
+	) -> B {
+		if self == Conviction::None {
+			return capital.checked_div(&10u8.into()).unwrap_or_else(Zero::zero)
+		}
+		let multiplier: u8 = match self {
+			Conviction::None | Conviction::Locked1x => 1,
+			Conviction::Locked2x => 2,
+			Conviction::Locked3x => 3,
+			Conviction::Locked4x => 4,
+			Conviction::Locked5x => 5,
+			Conviction::Locked6x => 6,
+		};
+		capital.checked_mul(&multiplier.into()).unwrap_or_else(B::max_value)
+	}
+}
+
+/// The conviction converts into its lock periods.
+impl<A: From<u32>> ConvertInto<A> for Conviction {
+	fn convert_into(&self) -> A {
+		self.lock_periods().into()
+	}
+}
+
+/// The native balance that is locked when voting.
+#[derive(
+	Encode,
+	Decode,
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	DefaultNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[codec(mel_bound(T: Config))]
+#[scale_info(skip_type_params(T))]
+pub struct LockedPledge<T: Config>(pub BalanceOf<T>, #[codec(skip)] PhantomData<T>);
+
+impl<T: Config> Copy for LockedPledge<T> {}
+
+impl<T: Config> LockedPledge<T> {
+	pub fn new(amount: BalanceOf<T>) -> Self {
+		Self(amount, PhantomData)
+	}
+
+	/// Lock the largest balance among the votes that are still locked, or remove the lock if
+	/// there is no such vote.
+	fn update_lock(who: &T::AccountId, locks: &[(T::DaoId, BalanceOf<T>, BlockNumberFor<T>)])
+	where
+		T::Currency: LockableCurrency<T::AccountId>,
+	{
+		match locks.iter().map(|(_, amount, _)| *amount).max() {
+			Some(amount) => <T as Config>::Currency::set_lock(
+				CONVICTION_ID,
+				who,
+				amount,
+				WithdrawReasons::all(),
+			),
+			None => <T as Config>::Currency::remove_lock(CONVICTION_ID, who),
+		}
+	}
+}
+
+impl<T: Config>
+	Pledge<BalanceOf<T>, T::AccountId, T::DaoId, Conviction, BlockNumberFor<T>, DispatchError>
+	for LockedPledge<T>
+where
+	T::Currency: LockableCurrency<T::AccountId>,
+{
+	fn try_vote(
+		&self,
+		who: &T::AccountId,
+		dao_id: &T::DaoId,
+		conviction: &Conviction,
+	) -> result::Result<(BalanceOf<T>, BlockNumberFor<T>), DispatchError> {
+		ensure!(<T as Config>::Currency::free_balance(who) >= self.0, Error::<T>::PledgeNotEnough);
+		let duration = VoteLockingPeriod::<T>::get(dao_id)
+			.saturating_mul(BlockNumberFor::<T>::from(conviction.lock_periods()));
+		// The referendum ends later, this is only used to release the votes in order.
+		let unlock_block = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+		VoteLocks::<T>::try_mutate(who, |locks| -> DResult {
+			locks
+				.try_push((*dao_id, self.0, unlock_block))
+				.map_err(|_| Error::<T>::TooManyVotes)?;
+			Self::update_lock(who, locks);
+			Ok(())
+		})?;
+		Ok((conviction.votes(self.0), duration))
+	}
+
+	fn vote_end_do(&self, who: &T::AccountId, dao_id: &T::DaoId) -> DResult {
+		VoteLocks::<T>::mutate_exists(who, |maybe_locks| {
+			let mut locks = maybe_locks.take().unwrap_or_default();
+			// Release the vote that is unlocked first, the others may still be locked.
+			if let Some(pos) = locks
+				.iter()
+				.enumerate()
+				.filter(|(_, (id, amount, _))| id == dao_id && *amount == self.0)
+				.min_by_key(|(_, (_, _, unlock_block))| *unlock_block)
+				.map(|(pos, _)| pos)
+			{
+				locks.remove(pos);
+			}
+			Self::update_lock(who, &locks);
+			if !locks.is_empty() {
+				*maybe_locks = Some(locks);
+			}
+		});
+		Ok(())
+	}
+
+	fn electorate(_dao_id: &T::DaoId) -> BalanceOf<T> {
+		<T as Config>::Currency::total_issuance()
+	}
+}
@@ -32,26 +32,36 @@
 
 extern crate core;
 
-pub use codec::{Decode, Encode};
-use sp_runtime::traits::Hash;
-use dao::{self, Vec};
+pub use codec::{Decode, Encode, MaxEncodedLen};
+use dao::{self, AfterDissolve, ApplyTemplate, DaoTemplateOf, Status, Vec};
  // use daos_sudo::UnfilteredDispatchable;
  use frame_support::traits::UnfilteredDispatchable;
-use frame_support::dispatch::{DispatchResult as DResult,};
+use frame_support::dispatch::{DispatchResult as DResult, GetDispatchInfo};
+use frame_support::{
+	ensure,
+	storage::with_storage_layer,
+	traits::{Bounded, QueryPreimage, StorePreimage},
+	weights::Weight,
+};
 pub use frame_support::{
 	traits::{Currency, Defensive, Get, ReservableCurrency},
 	BoundedVec, 
 };
 pub use pallet::*;
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 pub use sp_runtime::traits::{Saturating, Zero};
+use frame_system::pallet_prelude::BlockNumberFor;
 use sp_runtime::{
-	traits::{BlockNumberProvider, CheckedAdd, CheckedMul},
-	DispatchError,
+	traits::{AtLeast32BitUnsigned, CheckedDiv, CheckedMul, SaturatedConversion},
+	DispatchError, Perbill,
 };
-use sp_std::boxed::Box;
+use sp_core::H256;
 pub use sp_std::{fmt::Debug, result};
+pub use conviction::{Conviction, LockedPledge};
 pub use traits::*;
+pub use vote_threshold::{Approved, VoteThreshold};
 use weights::WeightInfo;
 // use daos_sudo::UnfilteredDispatchable;
 
@@ -67,29 +77,85 @@ mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod conviction;
+pub mod migrations;
 pub mod traits;
+pub mod vote_threshold;
 pub mod weights;
 
 /// Voting Statistics.
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq,  TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Tally<Balance> {
 	/// The number of aye votes, expressed in terms of post-conviction lock-vote.
 	pub ayes: Balance,
 	/// The number of nay votes, expressed in terms of post-conviction lock-vote.
 	pub nays: Balance,
+	/// The number of abstentions, which count toward the turnout but not the approval.
+	pub abstentions: Balance,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy> Tally<Balance> {
+	/// Add a vote to the tally.
+	pub fn add(&mut self, opinion: &Opinion<Balance>, vote_weight: Balance) {
+		let (ayes, nays, abstentions) = opinion.split(vote_weight);
+		self.ayes = self.ayes.saturating_add(ayes);
+		self.nays = self.nays.saturating_add(nays);
+		self.abstentions = self.abstentions.saturating_add(abstentions);
+	}
+
+	/// Remove a vote from the tally.
+	pub fn remove(&mut self, opinion: &Opinion<Balance>, vote_weight: Balance) {
+		let (ayes, nays, abstentions) = opinion.split(vote_weight);
+		self.ayes = self.ayes.saturating_sub(ayes);
+		self.nays = self.nays.saturating_sub(nays);
+		self.abstentions = self.abstentions.saturating_sub(abstentions);
+	}
+
+	/// All the votes in the tally.
+	pub fn turnout(&self) -> Balance {
+		self.ayes.saturating_add(self.nays).saturating_add(self.abstentions)
+	}
 }
 
 /// vote yes or no
-#[derive(Encode, Decode, Clone, PartialEq, Eq,  TypeInfo, Debug)]
-pub enum Opinion {
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Opinion<Balance> {
 	/// Agree.
 	AYES,
 	/// Reject.
 	NAYS,
+	/// Divide the vote weight between agreeing and rejecting in the ratio of `aye` to `nay`.
+	Split { aye: Balance, nay: Balance },
+	/// Neither agree nor reject, but count toward the turnout.
+	Abstain,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy> Opinion<Balance> {
+	/// Divide the vote weight into ayes, nays and abstentions.
+	pub fn split(&self, vote_weight: Balance) -> (Balance, Balance, Balance) {
+		match self {
+			Opinion::AYES => (vote_weight, Zero::zero(), Zero::zero()),
+			Opinion::NAYS => (Zero::zero(), vote_weight, Zero::zero()),
+			Opinion::Split { aye, nay } => {
+				let ayes =
+					Perbill::from_rational(*aye, aye.saturating_add(*nay)).mul_floor(vote_weight);
+				(ayes, vote_weight.saturating_sub(ayes), Zero::zero())
+			},
+			Opinion::Abstain => (Zero::zero(), Zero::zero(), vote_weight),
+		}
+	}
+
+	/// Whether the opinion can be voted.
+	fn is_valid(&self) -> bool {
+		!matches!(self, Opinion::Split { aye, nay } if aye.is_zero() && nay.is_zero())
+	}
 }
 
 /// Information about individual votes.
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq,  TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct VoteInfo<DaoId, ConcreteId, Pledge, BlockNumber, VoteWeight, Opinion, ReferendumIndex> {
 	/// The id of the Dao where the vote is located.
 	dao_id: DaoId,
@@ -107,8 +173,23 @@ pub struct VoteInfo<DaoId, ConcreteId, Pledge, BlockNumber, VoteWeight, Opinion,
 	referendum_index: ReferendumIndex,
 }
 
+/// The voting power that an account delegates to another account in a DAO.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct Delegation<AccountId, ConcreteId, Pledge, BlockNumber, VoteWeight> {
+	/// The account that votes with the delegated weight.
+	pub target: AccountId,
+	/// The specific group id mapped by Dao.
+	pub concrete_id: ConcreteId,
+	/// The specific thing that the delegation pledged.
+	pub pledge: Pledge,
+	/// The weight added to the votes of the target.
+	pub vote_weight: VoteWeight,
+	/// How long the pledge stays locked after the undelegation.
+	pub duration: BlockNumber,
+}
+
 /// Info regarding an ongoing referendum.
-#[derive(Encode, Decode, Clone, PartialEq, Eq,  TypeInfo)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
 pub struct ReferendumStatus<BlockNumber, Call, Balance> {
 	/// When voting on this referendum will end.
 	pub end: BlockNumber,
@@ -121,7 +202,7 @@ pub struct ReferendumStatus<BlockNumber, Call, Balance> {
 }
 
 /// Info regarding a referendum, present or past.
-#[derive(Encode, Decode, Clone, PartialEq, Eq,  TypeInfo)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
 pub enum ReferendumInfo<BlockNumber, Call, Balance> {
 	/// Referendum is happening, the arg is the block number at which it will end.
 	Ongoing(ReferendumStatus<BlockNumber, Call, Balance>),
@@ -129,6 +210,15 @@ pub enum ReferendumInfo<BlockNumber, Call, Balance> {
 	Finished { approved: bool, end: BlockNumber },
 }
 
+/// Work that is done automatically at the beginning of a block.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug)]
+pub enum Task<DaoId> {
+	/// Open a referendum for the best-backed public proposal of the DAO.
+	Launch(DaoId),
+	/// Enact the referendum of the DAO once its delay is over.
+	Enact(DaoId, ReferendumIndex),
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -139,6 +229,20 @@ pub mod pallet {
 	pub type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+	pub type CallOf<T> = <T as dao::Config>::Call;
+
+	pub type BoundedCallOf<T> = Bounded<CallOf<T>>;
+
+	pub type VoteInfoOf<T> = VoteInfo<
+		<T as dao::Config>::DaoId,
+		<T as dao::Config>::ConcreteId,
+		<T as Config>::Pledge,
+		BlockNumberFor<T>,
+		BalanceOf<T>,
+		Opinion<BalanceOf<T>>,
+		ReferendumIndex,
+	>;
+
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	pub trait Config: frame_system::Config + dao::Config {
@@ -150,15 +254,17 @@ pub mod pallet {
 			+ Copy
 			+ Parameter
 			+ Member
+			+ MaxEncodedLen
 			+ Pledge<
 				BalanceOf<Self>,
 				Self::AccountId,
 				Self::DaoId,
 				Self::Conviction,
-				u32,
+				BlockNumberFor<Self>,
 				DispatchError,
 			>;
-		/// The number of times the vote is magnified.
+		/// The conviction of a vote, which is passed to `Pledge::try_vote`. It converts into the
+		/// number of periods that the vote is locked for.
 		type Conviction: Clone
 			+ Default
 			+ Copy
@@ -167,13 +273,37 @@ pub mod pallet {
 			+ ConvertInto<BalanceOf<Self>>;
 		/// Operations related to native assets.
 		type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
+		/// Where the calls of the proposals are stored.
+		type Preimages: QueryPreimage + StorePreimage;
+		/// The maximum number of public proposals of a DAO at one time.
+		#[pallet::constant]
+		type MaxProposals: Get<u32>;
+		/// The maximum number of accounts that lock a deposit for a public proposal.
+		#[pallet::constant]
+		type MaxDeposits: Get<u32>;
+		/// The maximum number of reserves of an account that are waiting to be unreserved.
+		#[pallet::constant]
+		type MaxReserves: Get<u32>;
+		/// The maximum number of votes of an account that are waiting to be unlocked.
+		#[pallet::constant]
+		type MaxVotes: Get<u32>;
+		/// The maximum number of tasks that are done automatically in one block.
+		#[pallet::constant]
+		type MaxScheduledPerBlock: Get<u32>;
+		/// The maximum weight of the tasks that are done automatically in one block, with the
+		/// calls they dispatch.
+		#[pallet::constant]
+		type MaxScheduledWeight: Get<Weight>;
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(6);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
-	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Number of public proposals so for.
@@ -193,15 +323,15 @@ pub mod pallet {
 		StorageMap<_, Identity, T::DaoId, u32, ValueQuery, MaxPublicPropsOnEmpty>;
 
 	#[pallet::type_value]
-	pub fn LaunchPeriodOnEmpty<T: Config>() -> u32 {
-		u32::from(900u32)
+	pub fn LaunchPeriodOnEmpty<T: Config>() -> BlockNumberFor<T> {
+		BlockNumberFor::<T>::from(900u32)
 	}
 
 	/// How soon can a referendum be called.
 	#[pallet::storage]
 	#[pallet::getter(fn launch_period)]
 	pub type LaunchPeriod<T: Config> =
-		StorageMap<_, Identity, T::DaoId, u32, ValueQuery, LaunchPeriodOnEmpty<T>>;
+		StorageMap<_, Identity, T::DaoId, BlockNumberFor<T>, ValueQuery, LaunchPeriodOnEmpty<T>>;
 
 	/// Minimum stake per person when making public proposals.
 	#[pallet::storage]
@@ -210,50 +340,84 @@ pub mod pallet {
 		StorageMap<_, Identity, T::DaoId, BalanceOf<T>, ValueQuery>;
 
 	#[pallet::type_value]
-	pub fn VotingPeriodOnEmpty<T: Config>() -> u32 {
-		u32::from(900u32)
+	pub fn VotingPeriodOnEmpty<T: Config>() -> BlockNumberFor<T> {
+		BlockNumberFor::<T>::from(900u32)
 	}
 
 	/// How long each proposal can be voted on.
 	#[pallet::storage]
 	#[pallet::getter(fn voting_period)]
 	pub type VotingPeriod<T: Config> =
-		StorageMap<_, Identity, T::DaoId, u32, ValueQuery, VotingPeriodOnEmpty<T>>;
+		StorageMap<_, Identity, T::DaoId, BlockNumberFor<T>, ValueQuery, VotingPeriodOnEmpty<T>>;
 
 	#[pallet::type_value]
-	pub fn ReservePeriodOnEmpty<T: Config>() -> u32 {
-		u32::from(900u32)
+	pub fn ReservePeriodOnEmpty<T: Config>() -> BlockNumberFor<T> {
+		BlockNumberFor::<T>::from(900u32)
 	}
 
-	/// How long does it take to release the mortgage.
+	/// Deprecated, the deposits of the seconds are returned when the proposal is launched.
+	///
+	/// Still set by `set_rerserve_period`, the genesis and the templates, but no longer read.
 	#[pallet::storage]
 	#[pallet::getter(fn reserve_period)]
 	pub type ReservePeriod<T: Config> =
-		StorageMap<_, Identity, T::DaoId, u32, ValueQuery, ReservePeriodOnEmpty<T>>;
+		StorageMap<_, Identity, T::DaoId, BlockNumberFor<T>, ValueQuery, ReservePeriodOnEmpty<T>>;
 
 	#[pallet::type_value]
-	pub fn EnactmentPeriodOnEmpty<T: Config>() -> u32 {
-		u32::from(900u32)
+	pub fn EnactmentPeriodOnEmpty<T: Config>() -> BlockNumberFor<T> {
+		BlockNumberFor::<T>::from(900u32)
 	}
 
 	/// How soon after voting closes the proposal can be implemented.
 	#[pallet::storage]
 	#[pallet::getter(fn enactment_period)]
 	pub type EnactmentPeriod<T: Config> =
-		StorageMap<_, Identity, T::DaoId, u32, ValueQuery, EnactmentPeriodOnEmpty<T>>;
+		StorageMap<_, Identity, T::DaoId, BlockNumberFor<T>, ValueQuery, EnactmentPeriodOnEmpty<T>>;
+
+	#[pallet::type_value]
+	pub fn VoteLockingPeriodOnEmpty<T: Config>() -> BlockNumberFor<T> {
+		BlockNumberFor::<T>::from(900u32)
+	}
+
+	/// How long the balance is locked by a vote with `Conviction::Locked1x`.
+	#[pallet::storage]
+	#[pallet::getter(fn vote_locking_period)]
+	pub type VoteLockingPeriod<T: Config> = StorageMap<
+		_,
+		Identity,
+		T::DaoId,
+		BlockNumberFor<T>,
+		ValueQuery,
+		VoteLockingPeriodOnEmpty<T>,
+	>;
 
-	/// The public proposals. Unsorted. The second item is the proposal's hash.
+	/// The balances locked by the votes of an account with `LockedPledge`, and the earliest block
+	/// at which they can be unlocked.
+	#[pallet::storage]
+	#[pallet::getter(fn vote_locks)]
+	pub type VoteLocks<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<(T::DaoId, BalanceOf<T>, BlockNumberFor<T>), T::MaxVotes>,
+		ValueQuery,
+	>;
+
+	/// The public proposals. Unsorted. The call of a proposal is requested from the preimages
+	/// until the proposal is enacted or dropped.
 	#[pallet::storage]
 	#[pallet::getter(fn public_props)]
 	pub type PublicProps<T: Config> = StorageMap<
 		_,
 		Identity,
 		T::DaoId,
-		Vec<(PropIndex, T::Hash, <T as dao::Config>::Call, T::AccountId)>,
+		BoundedVec<(PropIndex, BoundedCallOf<T>, T::AccountId), T::MaxProposals>,
 		ValueQuery,
 	>;
 
-	/// Those who have locked a deposit.
+	/// Those who have locked a deposit for the proposal, the proposer first, and the amount that
+	/// each of them has locked. The deposits are returned when the proposal is launched or
+	/// withdrawn, and slashed when it is blacklisted.
 	///
 	/// TWOX-NOTE: Safe, as increasing integer keys are safe.
 	#[pallet::storage]
@@ -264,14 +428,20 @@ pub mod pallet {
 		T::DaoId,
 		Identity,
 		PropIndex,
-		(Vec<T::AccountId>, BalanceOf<T>),
+		(BoundedVec<T::AccountId, T::MaxDeposits>, BalanceOf<T>),
 	>;
 
-	/// Amount of proposal locked.
+	/// Amount of proposal locked by the seconds made before the deposits were tied to the
+	/// proposals, which `unlock` releases once their period is over.
 	#[pallet::storage]
 	#[pallet::getter(fn reserve_of)]
-	pub type ReserveOf<T: Config> =
-		StorageMap<_, Identity, T::AccountId, Vec<(BalanceOf<T>, u32)>, ValueQuery>;
+	pub type ReserveOf<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<(BalanceOf<T>, BlockNumberFor<T>), T::MaxReserves>,
+		ValueQuery,
+	>;
 
 	/// Referendum specific information.
 	#[pallet::storage]
@@ -282,7 +452,7 @@ pub mod pallet {
 		T::DaoId,
 		Identity,
 		ReferendumIndex,
-		ReferendumInfo<u32, <T as dao::Config>::Call, BalanceOf<T>>,
+		ReferendumInfo<BlockNumberFor<T>, BoundedCallOf<T>, BalanceOf<T>>,
 	>;
 
 	/// Number of referendums so far.
@@ -296,38 +466,151 @@ pub mod pallet {
 	#[pallet::getter(fn votes_of)]
 	pub type VotesOf<T: Config> = StorageMap<
 		_,
-		Identity,
+		Blake2_128Concat,
 		T::AccountId,
-		Vec<
+		BoundedVec<
 			VoteInfo<
 				T::DaoId,
 				T::ConcreteId,
 				T::Pledge,
-				u32,
+				BlockNumberFor<T>,
 				BalanceOf<T>,
-				Opinion,
+				Opinion<BalanceOf<T>>,
 				ReferendumIndex,
 			>,
+			T::MaxVotes,
 		>,
 		ValueQuery,
 	>;
 
+	/// The delegation of each account in each DAO.
+	#[pallet::storage]
+	#[pallet::getter(fn delegation_of)]
+	pub type Delegations<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		T::DaoId,
+		Blake2_128Concat,
+		T::AccountId,
+		Delegation<T::AccountId, T::ConcreteId, T::Pledge, BlockNumberFor<T>, BalanceOf<T>>,
+	>;
+
+	/// The total weight delegated to each account in each DAO.
+	#[pallet::storage]
+	#[pallet::getter(fn delegated_weight_of)]
+	pub type DelegatedWeightOf<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		T::DaoId,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// The pledges of the undelegated accounts, and the block heights that they can be unlocked.
+	#[pallet::storage]
+	#[pallet::getter(fn undelegated_of)]
+	pub type UndelegatedOf<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<(T::DaoId, T::ConcreteId, T::Pledge, BlockNumberFor<T>), T::MaxVotes>,
+		ValueQuery,
+	>;
+
 	/// Minimum voting weight required for each external transaction.
 	#[pallet::storage]
 	#[pallet::getter(fn min_vote_weight_of)]
 	pub type MinVoteWeightOf<T: Config> =
 		StorageDoubleMap<_, Identity, T::DaoId, Identity, T::CallId, BalanceOf<T>, ValueQuery>;
 
-	/// When the referendum was last launched.
+	/// The threshold that the referendums of each external transaction need to pass.
+	///
+	/// The referendums pass if there are at least as many ayes as nays without a threshold.
+	#[pallet::storage]
+	#[pallet::getter(fn vote_threshold_of)]
+	pub type VoteThresholdOf<T: Config> =
+		StorageDoubleMap<_, Identity, T::DaoId, Identity, T::CallId, VoteThreshold>;
+
+	/// The threshold of a referendum, which takes precedence over the one of its transaction.
+	#[pallet::storage]
+	#[pallet::getter(fn referendum_threshold_of)]
+	pub type ReferendumThresholdOf<T: Config> =
+		StorageDoubleMap<_, Identity, T::DaoId, Identity, ReferendumIndex, VoteThreshold>;
+
+	/// The launch period after which the next referendum can be opened.
 	#[pallet::storage]
 	#[pallet::getter(fn launch_tag)]
 	pub type LaunchTag<T: Config> = StorageMap<_, Identity, T::DaoId, u32, ValueQuery>;
 
+	/// The tasks that are done automatically at the beginning of each block.
+	#[pallet::storage]
+	#[pallet::getter(fn agenda)]
+	pub type Agenda<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<Task<T::DaoId>, T::MaxScheduledPerBlock>,
+		ValueQuery,
+	>;
+
+	/// The block at which the next referendum of the DAO is opened automatically.
+	#[pallet::storage]
+	#[pallet::getter(fn next_launch)]
+	pub type NextLaunch<T: Config> = StorageMap<_, Identity, T::DaoId, BlockNumberFor<T>>;
+
+	/// The proposals that the DAO has blacklisted and that can't be proposed again.
+	#[pallet::storage]
+	pub type Blacklist<T: Config> =
+		StorageDoubleMap<_, Identity, T::DaoId, Identity, H256, (), OptionQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// The minimum voting weight of each call of each DAO.
+		pub min_vote_weights: Vec<(T::DaoId, T::CallId, BalanceOf<T>)>,
+		/// How soon can a referendum be called in each DAO.
+		pub launch_periods: Vec<(T::DaoId, BlockNumberFor<T>)>,
+		/// How long each proposal can be voted on in each DAO.
+		pub voting_periods: Vec<(T::DaoId, BlockNumberFor<T>)>,
+		/// Deprecated, see `ReservePeriod`.
+		pub reserve_periods: Vec<(T::DaoId, BlockNumberFor<T>)>,
+		/// How soon after voting closes the proposal can be implemented in each DAO.
+		pub enactment_periods: Vec<(T::DaoId, BlockNumberFor<T>)>,
+		/// How long the balance is locked by a vote in each DAO.
+		pub vote_locking_periods: Vec<(T::DaoId, BlockNumberFor<T>)>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			for (dao_id, call_id, min_vote_weight) in self.min_vote_weights.iter() {
+				MinVoteWeightOf::<T>::insert(dao_id, call_id, min_vote_weight);
+			}
+			for (dao_id, period) in self.launch_periods.iter() {
+				LaunchPeriod::<T>::insert(dao_id, period);
+			}
+			for (dao_id, period) in self.voting_periods.iter() {
+				VotingPeriod::<T>::insert(dao_id, period);
+			}
+			for (dao_id, period) in self.reserve_periods.iter() {
+				ReservePeriod::<T>::insert(dao_id, period);
+			}
+			for (dao_id, period) in self.enactment_periods.iter() {
+				EnactmentPeriod::<T>::insert(dao_id, period);
+			}
+			for (dao_id, period) in self.vote_locking_periods.iter() {
+				VoteLockingPeriod::<T>::insert(dao_id, period);
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// initiate a proposal.
-		Proposed(T::DaoId, T::Hash),
+		Proposed(T::DaoId, H256),
 		/// Others support initiating proposals.
 		Second(T::DaoId, BalanceOf<T>),
 		/// Open a referendum.
@@ -347,15 +630,40 @@ pub mod pallet {
 		/// Set the maximum number of proposals at the same time.
 		SetMaxPublicProps { dao_id: T::DaoId, max: u32 },
 		/// Set the referendum interval.
-		SetLaunchPeriod { dao_id: T::DaoId, period: u32 },
+		SetLaunchPeriod { dao_id: T::DaoId, period: BlockNumberFor<T> },
 		/// Set the minimum amount a proposal needs to stake.
 		SetMinimumDeposit { dao_id: T::DaoId, min: BalanceOf<T> },
 		/// Set the voting length of the referendum.
-		SetVotingPeriod { dao_id: T::DaoId, period: u32 },
-		/// Set the length of time that can be unreserved.
-		SetReservePeriod { dao_id: T::DaoId, period: u32 },
+		SetVotingPeriod { dao_id: T::DaoId, period: BlockNumberFor<T> },
+		/// Set the length of time that can be unreserved, deprecated.
+		SetReservePeriod { dao_id: T::DaoId, period: BlockNumberFor<T> },
 		/// Set the time to delay the execution of the proposal.
-		SetEnactmentPeriod { dao_id: T::DaoId, period: u32 },
+		SetEnactmentPeriod { dao_id: T::DaoId, period: BlockNumberFor<T> },
+		/// Set how long the balance is locked by a vote.
+		SetVoteLockingPeriod { dao_id: T::DaoId, period: BlockNumberFor<T> },
+		/// Delegate the voting power to another account.
+		Delegated {
+			dao_id: T::DaoId,
+			who: T::AccountId,
+			target: T::AccountId,
+			pledge: T::Pledge,
+			conviction: T::Conviction,
+			vote_weight: BalanceOf<T>,
+		},
+		/// Take back the voting power delegated to another account.
+		Undelegated { dao_id: T::DaoId, who: T::AccountId, target: T::AccountId },
+		/// Set the threshold that the referendums of a call need to pass.
+		SetVoteThreshold { dao_id: T::DaoId, call_id: T::CallId, threshold: Option<VoteThreshold> },
+		/// Set the threshold that a referendum needs to pass.
+		SetReferendumThreshold {
+			dao_id: T::DaoId,
+			index: ReferendumIndex,
+			threshold: Option<VoteThreshold>,
+		},
+		/// The proposer withdrew the proposal and the deposits are returned.
+		Withdrawn { dao_id: T::DaoId, index: PropIndex },
+		/// The proposal is blacklisted and the deposits of its public proposals are slashed.
+		Blacklisted { dao_id: T::DaoId, proposal_hash: H256 },
 	}
 
 	// Errors inform users that something went wrong.
@@ -367,6 +675,12 @@ pub mod pallet {
 		DepositTooLow,
 		/// Maximum number of proposals reached.
 		TooManyProposals,
+		/// Maximum number of accounts locking a deposit for the proposal reached.
+		TooManyDeposits,
+		/// Maximum number of reserves of the account reached.
+		TooManyReserves,
+		/// Maximum number of votes of the account reached.
+		TooManyVotes,
 		/// Proposal does not exist.
 		ProposalMissing,
 		/// There are no proposals in progress.
@@ -389,43 +703,129 @@ pub mod pallet {
 		VoteWeightTooLow,
 		///
 		PledgeNotEnough,
+		/// The account has delegated its voting power in the DAO.
+		AlreadyDelegating,
+		/// The account has not delegated its voting power in the DAO.
+		NotDelegating,
+		/// The voting power can not be delegated to oneself.
+		DelegateToSelf,
+		/// The account has votes in the ongoing referendums of the DAO.
+		AlreadyVoting,
+		/// A split vote has neither ayes nor nays.
+		InvalidSplit,
+		/// The preimage of the proposal is not noted.
+		PreimageMissing,
+		/// Only the proposer can withdraw the proposal.
+		NotProposer,
+		/// The proposal is blacklisted.
+		ProposalBlacklisted,
+		/// The voting power can not be delegated to an account that delegates itself.
+		TargetDelegating,
+		/// The account that others delegate to can not delegate itself.
+		HasDelegators,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Open and enact the referendums that are scheduled for this block.
+		///
+		/// At most `MaxScheduledPerBlock` tasks are done in one block, and no more than
+		/// `MaxScheduledWeight`. A task that doesn't fit is moved to the next block, a task that
+		/// fails or is heavier than `MaxScheduledWeight` is dropped and can still be done by the
+		/// extrinsic.
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let max_weight = T::MaxScheduledWeight::get();
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			let next = now.saturating_add(1u32.into());
+			for task in Agenda::<T>::take(now) {
+				match task {
+					Task::Launch(dao_id) => {
+						let task_weight = <T as pallet::Config>::WeightInfo::open_table()
+							.saturating_add(T::DbWeight::get().reads_writes(4, 3));
+						if weight.saturating_add(task_weight).any_gt(max_weight) {
+							weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 2));
+							if task_weight.any_gt(max_weight) {
+								NextLaunch::<T>::remove(dao_id);
+							} else {
+								NextLaunch::<T>::insert(dao_id, Self::schedule(next, task));
+							}
+							continue
+						}
+						weight.saturating_accrue(task_weight);
+						NextLaunch::<T>::remove(dao_id);
+						let _ = with_storage_layer(|| Self::do_open_table(dao_id));
+						if !PublicProps::<T>::get(dao_id).is_empty() {
+							Self::schedule_launch(dao_id);
+						}
+					},
+					Task::Enact(dao_id, index) => {
+						weight.saturating_accrue(T::DbWeight::get().reads(2));
+						let task_weight = <T as pallet::Config>::WeightInfo::enact_proposal()
+							.saturating_add(Self::proposal_weight(dao_id, index));
+						if weight.saturating_add(task_weight).any_gt(max_weight) {
+							if !task_weight.any_gt(max_weight) {
+								weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+								Self::schedule(next, task);
+							}
+							continue
+						}
+						weight.saturating_accrue(task_weight);
+						let _ = with_storage_layer(|| Self::do_enact_proposal(dao_id, index));
+					},
+				}
+			}
+			weight
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// initiate a proposal.
+		///
+		/// The call of the proposal is checked now if its preimage is noted, and is always
+		/// checked again when it is enacted.
+		#[pallet::call_index(0)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::propose())]
 		pub fn propose(
 			origin: OriginFor<T>,
 			dao_id: T::DaoId,
-			proposal: Box<<T as dao::Config>::Call>,
+			proposal: BoundedCallOf<T>,
 			#[pallet::compact] value: BalanceOf<T>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			ensure!(
-				dao::Pallet::<T>::try_get_concrete_id(dao_id)?.contains(*proposal.clone()),
-				dao::Error::<T>::InVailCall
-			);
+			if let Ok((call, _)) = T::Preimages::peek(&proposal) {
+				Self::ensure_proposal_allowed(dao_id, &call)?;
+			}
 			ensure!(value >= MinimumDeposit::<T>::get(dao_id), Error::<T>::DepositTooLow);
 
-			let proposal_hash = T::Hashing::hash_of(&proposal);
+			let proposal_hash = proposal.hash();
+			ensure!(
+				!Blacklist::<T>::contains_key(dao_id, proposal_hash),
+				Error::<T>::ProposalBlacklisted
+			);
 			let index = Self::public_prop_count(dao_id);
 			let real_prop_count = PublicProps::<T>::decode_len(dao_id).unwrap_or(0) as u32;
 			let max_proposals = MaxPublicProps::<T>::get(dao_id);
 			ensure!(real_prop_count < max_proposals, Error::<T>::TooManyProposals);
 
-			T::Currency::reserve(&who, value)?;
+			<T as pallet::Config>::Currency::reserve(&who, value)?;
 
 			PublicPropCount::<T>::insert(dao_id, index + 1);
-			<DepositOf<T>>::insert(dao_id, index, (&[&who][..], value));
+			let depositors = BoundedVec::<_, T::MaxDeposits>::try_from(sp_std::vec![who.clone()])
+				.map_err(|_| Error::<T>::TooManyDeposits)?;
+			<DepositOf<T>>::insert(dao_id, index, (depositors, value));
 
-			<PublicProps<T>>::append(dao_id, (index, proposal_hash, *proposal, who));
+			T::Preimages::hold(&proposal);
+			<PublicProps<T>>::try_append(dao_id, (index, proposal, who))
+				.map_err(|_| Error::<T>::TooManyProposals)?;
+			Self::schedule_launch(dao_id);
 
 			Self::deposit_event(Event::<T>::Proposed(dao_id, proposal_hash));
 			Ok(().into())
 		}
 
 		/// Others support initiating proposals.
+		#[pallet::call_index(1)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::second())]
 		pub fn second(
 			origin: OriginFor<T>,
@@ -436,38 +836,30 @@ pub mod pallet {
 			let mut deposit =
 				Self::deposit_of(dao_id, proposal).ok_or(Error::<T>::ProposalMissing)?;
 			let deposit_amount = deposit.1;
-			T::Currency::reserve(&who, deposit_amount)?;
-			deposit.0.push(who.clone());
+			<T as pallet::Config>::Currency::reserve(&who, deposit_amount)?;
+			deposit.0.try_push(who.clone()).map_err(|_| Error::<T>::TooManyDeposits)?;
 			<DepositOf<T>>::insert(dao_id, proposal, deposit);
-			let unreserved_block = Self::now()
-				.checked_add(ReservePeriod::<T>::get(dao_id))
-				.ok_or(Error::<T>::Overflow)?;
-			ReserveOf::<T>::append(who, (deposit_amount, unreserved_block));
 			Self::deposit_event(Event::<T>::Second(dao_id, deposit_amount));
 
 			Ok(().into())
 		}
 
 		/// Open a referendum.
+		#[pallet::call_index(2)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::open_table())]
 		pub fn open_table(origin: OriginFor<T>, dao_id: T::DaoId) -> DispatchResultWithPostInfo {
 			let _ = ensure_signed(origin)?;
-			let tag = LaunchTag::<T>::get(dao_id);
-			let now = Self::now();
-			let dao_start_time = dao::Pallet::<T>::try_get_dao(dao_id)?.start_block;
-			// (now - dao_start_time) / LaunchPeriod > tag
-			ensure!(
-				tag.checked_mul(LaunchPeriod::<T>::get(dao_id)).ok_or(Error::<T>::Overflow)? <
-					(now - dao_start_time),
-				Error::<T>::NotTableTime
-			);
-			let index = Self::launch_public(dao_id)?;
-			Self::deposit_event(Event::<T>::StartTable(dao_id, index));
+			Self::do_open_table(dao_id)?;
 
 			Ok(().into())
 		}
 
 		/// Vote for the referendum
+		///
+		/// An account has only one vote in a referendum, voting again replaces the previous vote.
+		/// The weight of a split vote is divided between ayes and nays, and the weight of an
+		/// abstention only counts toward the turnout.
+		#[pallet::call_index(3)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::vote_for_referendum())]
 		pub fn vote_for_referendum(
 			origin: OriginFor<T>,
@@ -475,9 +867,11 @@ pub mod pallet {
 			index: ReferendumIndex,
 			pledge: T::Pledge,
 			conviction: T::Conviction,
-			opinion: Opinion,
+			opinion: Opinion<BalanceOf<T>>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
+			ensure!(opinion.is_valid(), Error::<T>::InvalidSplit);
+			ensure!(!Delegations::<T>::contains_key(dao_id, &who), Error::<T>::AlreadyDelegating);
 			let now = Self::now();
 			let mut vote_weight = BalanceOf::<T>::from(0u32);
 
@@ -489,29 +883,38 @@ pub mod pallet {
 					if let ReferendumInfo::Ongoing(ref mut x) = info {
 						if x.end > now {
 							let concrete_id = dao::Pallet::<T>::try_get_concrete_id(dao_id)?;
+							let mut votes = VotesOf::<T>::get(&who);
+							let previous = votes
+								.iter()
+								.position(|v| v.dao_id == dao_id && v.referendum_index == index);
+							if let Some(pos) = previous {
+								let old = &votes[pos];
+								old.pledge.vote_end_do(&who, &dao_id)?;
+								x.tally.remove(&old.opinion, old.vote_weight);
+							}
 							let vote_result = pledge.try_vote(&who, &dao_id, &conviction)?;
-							vote_weight = vote_result.0;
+							vote_weight = vote_result
+								.0
+								.saturating_add(DelegatedWeightOf::<T>::get(dao_id, &who));
 							let duration = vote_result.1;
-							match opinion {
-								Opinion::NAYS => {
-									x.tally.nays += vote_weight;
-								},
-								Opinion::AYES => {
-									x.tally.ayes += vote_weight;
-								},
+							x.tally.add(&opinion, vote_weight);
+							let vote = VoteInfo {
+								dao_id,
+								concrete_id,
+								pledge,
+								opinion,
+								vote_weight,
+								// The vote weight counts until the referendum ends, so the lock
+								// lasts at least until then.
+								unlock_block: x.end.max(now).saturating_add(duration),
+								referendum_index: index,
 							};
-							VotesOf::<T>::append(
-								&who,
-								VoteInfo {
-									dao_id,
-									concrete_id,
-									pledge,
-									opinion,
-									vote_weight,
-									unlock_block: now + duration,
-									referendum_index: index,
-								},
-							);
+							match previous {
+								Some(pos) => votes[pos] = vote,
+								None =>
+									votes.try_push(vote).map_err(|_| Error::<T>::TooManyVotes)?,
+							}
+							VotesOf::<T>::insert(&who, votes);
 						} else {
 							return Err(Error::<T>::VoteEnd)?
 						}
@@ -528,6 +931,7 @@ pub mod pallet {
 		}
 
 		/// Cancel a vote on a referendum
+		#[pallet::call_index(4)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::cancel_vote())]
 		pub fn cancel_vote(
 			origin: OriginFor<T>,
@@ -545,19 +949,11 @@ pub mod pallet {
 						if x.end > now {
 							let mut votes = VotesOf::<T>::get(&who);
 							votes.retain(|h| {
-								if h.referendum_index == index &&
+								if h.dao_id == dao_id &&
+									h.referendum_index == index &&
 									h.pledge.vote_end_do(&who, &dao_id).is_ok()
 								{
-									match h.opinion {
-										Opinion::NAYS => {
-											x.tally.nays =
-												x.tally.nays.saturating_sub(h.vote_weight);
-										},
-										_ => {
-											x.tally.ayes =
-												x.tally.ayes.saturating_sub(h.vote_weight);
-										},
-									};
+									x.tally.remove(&h.opinion, h.vote_weight);
 									false
 								} else {
 									true
@@ -580,6 +976,7 @@ pub mod pallet {
 		}
 
 		/// Vote and execute the transaction corresponding to the proposa
+		#[pallet::call_index(5)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::enact_proposal())]
 		pub fn enact_proposal(
 			origin: OriginFor<T>,
@@ -587,62 +984,13 @@ pub mod pallet {
 			index: ReferendumIndex,
 		) -> DispatchResultWithPostInfo {
 			ensure_signed(origin)?;
-			let now = Self::now();
-			let mut approved = false;
-			let info =
-				ReferendumInfoOf::<T>::get(dao_id, index).ok_or(Error::<T>::ReferendumNotExists)?;
-			match info {
-				ReferendumInfo::Ongoing(x) =>
-					if x.end > now {
-						return Err(Error::<T>::VoteNotEnd)?
-					} else if x.end.saturating_add(x.delay) > now {
-						return Err(Error::<T>::InDelayTime)?
-					} else {
-						{
-							let call_id: T::CallId =
-								TryFrom::<<T as dao::Config>::Call>::try_from(x.proposal.clone())
-									.unwrap_or_default();
-
-							if x.tally.ayes.saturating_add(x.tally.nays) >=
-								MinVoteWeightOf::<T>::get(dao_id, call_id)
-							{
-								if x.tally.ayes >= x.tally.nays {
-									approved = true;
-									let res = x.proposal.dispatch_bypass_filter(
-										frame_system::RawOrigin::Signed(
-											dao::Pallet::<T>::try_get_dao_account_id(dao_id)?,
-										)
-										.into(),
-									);
-									Self::deposit_event(Event::EnactProposal {
-										dao_id,
-										index,
-										result: res.map(|_| ()).map_err(|e| e.error),
-									});
-								} else {
-									Self::deposit_event(Event::EnactProposal {
-										dao_id,
-										index,
-										result: Err(Error::<T>::VoteEndButNotPass)?,
-									});
-								}
-							} else {
-								return Err(Error::<T>::VoteWeightTooLow)?
-							}
-						}
-					},
-				_ => return Err(Error::<T>::ReferendumFinished)?,
-			}
-			ReferendumInfoOf::<T>::insert(
-				dao_id,
-				index,
-				ReferendumInfo::Finished { approved, end: now },
-			);
+			Self::do_enact_proposal(dao_id, index)?;
 
 			Ok(().into())
 		}
 
 		/// Unlock
+		#[pallet::call_index(6)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::unlock())]
 		pub fn unlock(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
@@ -655,7 +1003,7 @@ pub mod pallet {
 					if h.1 > now {
 						true
 					} else {
-						T::Currency::unreserve(&who, h.0);
+						<T as pallet::Config>::Currency::unreserve(&who, h.0);
 						total += h.0;
 						false
 					}
@@ -668,7 +1016,12 @@ pub mod pallet {
 			{
 				let mut votes = VotesOf::<T>::get(&who);
 				votes.retain(|h| {
-					if h.unlock_block > now || h.pledge.vote_end_do(&who, &h.dao_id).is_err() {
+					// The votes of a dissolved DAO can be unlocked at any time.
+					let dissolved = dao::Pallet::<T>::try_get_status(h.dao_id) ==
+						Ok(Status::Dissolved);
+					if (h.unlock_block > now && !dissolved) ||
+						h.pledge.vote_end_do(&who, &h.dao_id).is_err()
+					{
 						true
 					} else {
 						Self::deposit_event(Event::<T>::Unlock(
@@ -682,12 +1035,29 @@ pub mod pallet {
 				VotesOf::<T>::insert(&who, votes);
 			}
 
+			//
+			{
+				let mut undelegated = UndelegatedOf::<T>::get(&who);
+				undelegated.retain(|(dao_id, concrete_id, pledge, unlock_block)| {
+					let dissolved =
+						dao::Pallet::<T>::try_get_status(*dao_id) == Ok(Status::Dissolved);
+					if (*unlock_block > now && !dissolved) ||
+						pledge.vote_end_do(&who, dao_id).is_err()
+					{
+						true
+					} else {
+						Self::deposit_event(Event::<T>::Unlock(who.clone(), *concrete_id, *pledge));
+						false
+					}
+				});
+				UndelegatedOf::<T>::insert(&who, undelegated);
+			}
+
 			Ok(().into())
 		}
 
-		/// call id:301
-		///
 		/// Set Origin for each Call.
+		#[pallet::call_index(7)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_min_vote_weight_for_every_call())]
 		pub fn set_min_vote_weight_for_every_call(
 			origin: OriginFor<T>,
@@ -702,9 +1072,8 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		/// call id:302
-		///
 		/// Set the maximum number of proposals at the same time
+		#[pallet::call_index(8)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_max_public_props())]
 		pub fn set_max_public_props(
 			origin: OriginFor<T>,
@@ -718,14 +1087,13 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		/// call id:303
-		///
 		/// Set the referendum interval
+		#[pallet::call_index(9)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_launch_period())]
 		pub fn set_launch_period(
 			origin: OriginFor<T>,
 			dao_id: T::DaoId,
-			period: u32,
+			period: BlockNumberFor<T>,
 		) -> DispatchResultWithPostInfo {
 			dao::Pallet::<T>::ensrue_dao_root(origin, dao_id)?;
 			LaunchPeriod::<T>::insert(dao_id, period);
@@ -734,9 +1102,8 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		/// call id:304
-		///
 		/// Set the minimum amount a proposal needs to stake
+		#[pallet::call_index(10)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_minimum_deposit())]
 		pub fn set_minimum_deposit(
 			origin: OriginFor<T>,
@@ -750,14 +1117,13 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		/// call id:305
-		///
 		/// Set the voting length of the referendum
+		#[pallet::call_index(11)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_voting_period())]
 		pub fn set_voting_period(
 			origin: OriginFor<T>,
 			dao_id: T::DaoId,
-			period: u32,
+			period: BlockNumberFor<T>,
 		) -> DispatchResultWithPostInfo {
 			dao::Pallet::<T>::ensrue_dao_root(origin, dao_id)?;
 			VotingPeriod::<T>::insert(dao_id, period);
@@ -766,14 +1132,16 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		/// call id:306
-		///
 		/// Set the length of time that can be unreserved
+		///
+		/// Deprecated, `ReservePeriod` is no longer read since the deposits of the seconds are
+		/// returned when the proposal is launched. Kept so that the call indexes don't change.
+		#[pallet::call_index(12)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_rerserve_period())]
 		pub fn set_rerserve_period(
 			origin: OriginFor<T>,
 			dao_id: T::DaoId,
-			period: u32,
+			period: BlockNumberFor<T>,
 		) -> DispatchResultWithPostInfo {
 			dao::Pallet::<T>::ensrue_dao_root(origin, dao_id)?;
 			ReservePeriod::<T>::insert(dao_id, period);
@@ -782,14 +1150,13 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		/// call id:307
-		///
 		/// Set the time to delay the execution of the proposal
+		#[pallet::call_index(13)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_enactment_period())]
 		pub fn set_enactment_period(
 			origin: OriginFor<T>,
 			dao_id: T::DaoId,
-			period: u32,
+			period: BlockNumberFor<T>,
 		) -> DispatchResultWithPostInfo {
 			dao::Pallet::<T>::ensrue_dao_root(origin, dao_id)?;
 			EnactmentPeriod::<T>::insert(dao_id, period);
@@ -797,6 +1164,192 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		/// Set how long the balance is locked by a vote with `Conviction::Locked1x`
+		#[pallet::call_index(14)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_vote_locking_period())]
+		pub fn set_vote_locking_period(
+			origin: OriginFor<T>,
+			dao_id: T::DaoId,
+			period: BlockNumberFor<T>,
+		) -> DispatchResultWithPostInfo {
+			dao::Pallet::<T>::ensrue_dao_root(origin, dao_id)?;
+			VoteLockingPeriod::<T>::insert(dao_id, period);
+			Self::deposit_event(Event::<T>::SetVoteLockingPeriod { dao_id, period });
+
+			Ok(().into())
+		}
+
+		/// Delegate the voting power to another account.
+		///
+		/// The delegated weight is added to the votes of `target` in the ongoing and future
+		/// referendums of the DAO, until it is undelegated. The weight is not delegated any
+		/// further, so `target` can not be delegating and the delegate can not delegate itself.
+		#[pallet::call_index(15)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::delegate())]
+		pub fn delegate(
+			origin: OriginFor<T>,
+			dao_id: T::DaoId,
+			target: T::AccountId,
+			pledge: T::Pledge,
+			conviction: T::Conviction,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(who != target, Error::<T>::DelegateToSelf);
+			ensure!(!Delegations::<T>::contains_key(dao_id, &who), Error::<T>::AlreadyDelegating);
+			ensure!(!Delegations::<T>::contains_key(dao_id, &target), Error::<T>::TargetDelegating);
+			ensure!(!Self::is_voting(&who, dao_id), Error::<T>::AlreadyVoting);
+			ensure!(DelegatedWeightOf::<T>::get(dao_id, &who).is_zero(), Error::<T>::HasDelegators);
+			let concrete_id = dao::Pallet::<T>::try_get_concrete_id(dao_id)?;
+			let (vote_weight, duration) = pledge.try_vote(&who, &dao_id, &conviction)?;
+
+			DelegatedWeightOf::<T>::mutate(dao_id, &target, |w| *w = w.saturating_add(vote_weight));
+			Self::update_votes_of_delegate(dao_id, &target, vote_weight, true);
+			Delegations::<T>::insert(
+				dao_id,
+				&who,
+				Delegation { target: target.clone(), concrete_id, pledge, vote_weight, duration },
+			);
+
+			Self::deposit_event(Event::<T>::Delegated {
+				dao_id,
+				who,
+				target,
+				pledge,
+				conviction,
+				vote_weight,
+			});
+			Ok(().into())
+		}
+
+		/// Take back the voting power delegated to another account.
+		///
+		/// The pledge can be unlocked by `unlock` after the duration of the delegation.
+		#[pallet::call_index(16)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::undelegate())]
+		pub fn undelegate(origin: OriginFor<T>, dao_id: T::DaoId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let delegation =
+				Delegations::<T>::take(dao_id, &who).ok_or(Error::<T>::NotDelegating)?;
+
+			UndelegatedOf::<T>::try_append(
+				&who,
+				(
+					dao_id,
+					delegation.concrete_id,
+					delegation.pledge,
+					Self::now().saturating_add(delegation.duration),
+				),
+			)
+			.map_err(|_| Error::<T>::TooManyVotes)?;
+			DelegatedWeightOf::<T>::mutate_exists(dao_id, &delegation.target, |w| {
+				*w = w
+					.map(|weight| weight.saturating_sub(delegation.vote_weight))
+					.filter(|weight| !weight.is_zero());
+			});
+			Self::update_votes_of_delegate(
+				dao_id,
+				&delegation.target,
+				delegation.vote_weight,
+				false,
+			);
+
+			Self::deposit_event(Event::<T>::Undelegated { dao_id, who, target: delegation.target });
+			Ok(().into())
+		}
+
+		/// Set the threshold that the referendums of a call need to pass
+		///
+		/// With `None`, the referendums pass if the ayes are at least the nays, so a tie passes.
+		/// Set `VoteThreshold::SimpleMajority` to reject ties.
+		#[pallet::call_index(17)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_vote_threshold_for_every_call())]
+		pub fn set_vote_threshold_for_every_call(
+			origin: OriginFor<T>,
+			dao_id: T::DaoId,
+			call_id: T::CallId,
+			threshold: Option<VoteThreshold>,
+		) -> DispatchResultWithPostInfo {
+			dao::Pallet::<T>::ensrue_dao_root(origin, dao_id)?;
+			VoteThresholdOf::<T>::set(dao_id, call_id, threshold);
+			Self::deposit_event(Event::<T>::SetVoteThreshold { dao_id, call_id, threshold });
+
+			Ok(().into())
+		}
+
+		/// Set the threshold that an ongoing referendum needs to pass
+		#[pallet::call_index(18)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_referendum_threshold())]
+		pub fn set_referendum_threshold(
+			origin: OriginFor<T>,
+			dao_id: T::DaoId,
+			index: ReferendumIndex,
+			threshold: Option<VoteThreshold>,
+		) -> DispatchResultWithPostInfo {
+			dao::Pallet::<T>::ensrue_dao_root(origin, dao_id)?;
+			match ReferendumInfoOf::<T>::get(dao_id, index) {
+				Some(ReferendumInfo::Ongoing(_)) => {},
+				Some(ReferendumInfo::Finished { .. }) => Err(Error::<T>::ReferendumFinished)?,
+				None => Err(Error::<T>::ReferendumNotExists)?,
+			}
+			ReferendumThresholdOf::<T>::set(dao_id, index, threshold);
+			Self::deposit_event(Event::<T>::SetReferendumThreshold { dao_id, index, threshold });
+
+			Ok(().into())
+		}
+
+		/// Withdraw a public proposal, the deposits are returned to the proposer and the
+		/// seconders.
+		#[pallet::call_index(19)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::withdraw_proposal())]
+		pub fn withdraw_proposal(
+			origin: OriginFor<T>,
+			dao_id: T::DaoId,
+			#[pallet::compact] index: PropIndex,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			PublicProps::<T>::try_mutate(dao_id, |props| -> DResult {
+				let position =
+					props.iter().position(|p| p.0 == index).ok_or(Error::<T>::ProposalMissing)?;
+				ensure!(props[position].2 == who, Error::<T>::NotProposer);
+				let (_, proposal, _) = props.remove(position);
+				T::Preimages::drop(&proposal);
+				Ok(())
+			})?;
+			Self::refund_deposits(dao_id, index);
+			Self::deposit_event(Event::<T>::Withdrawn { dao_id, index });
+			Ok(().into())
+		}
+
+		/// Blacklist a proposal so that it can't be proposed again. Its public proposals are
+		/// removed and their deposits are slashed, and its ongoing referendums are rejected
+		/// when they end.
+		#[pallet::call_index(20)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::blacklist())]
+		pub fn blacklist(
+			origin: OriginFor<T>,
+			dao_id: T::DaoId,
+			proposal_hash: H256,
+		) -> DispatchResultWithPostInfo {
+			dao::Pallet::<T>::ensrue_dao_root(origin, dao_id)?;
+			Blacklist::<T>::insert(dao_id, proposal_hash, ());
+			PublicProps::<T>::mutate(dao_id, |props| {
+				props.retain(|(index, proposal, _)| {
+					if proposal.hash() != proposal_hash {
+						return true
+					}
+					T::Preimages::drop(proposal);
+					if let Some((depositors, deposit)) = DepositOf::<T>::take(dao_id, index) {
+						for who in depositors {
+							<T as pallet::Config>::Currency::slash_reserved(&who, deposit);
+						}
+					}
+					false
+				})
+			});
+			Self::deposit_event(Event::<T>::Blacklisted { dao_id, proposal_hash });
+			Ok(().into())
+		}
 	}
 }
 
@@ -805,6 +1358,217 @@ impl<T: Config> Pallet<T> {
 		Self::deposit_of(dao_id, proposal).map(|(l, d)| d.saturating_mul((l.len() as u32).into()))
 	}
 
+	/// The open public proposals of the DAO and their proposers.
+	pub fn open_proposals(dao_id: T::DaoId) -> Vec<(PropIndex, H256, T::AccountId)> {
+		Self::public_props(dao_id)
+			.into_iter()
+			.map(|(index, proposal, who)| (index, proposal.hash(), who))
+			.collect()
+	}
+
+	/// The tally of the ongoing referendum.
+	pub fn referendum_tally(
+		dao_id: T::DaoId,
+		index: ReferendumIndex,
+	) -> Option<Tally<BalanceOf<T>>> {
+		match Self::referendum_info(dao_id, index)? {
+			ReferendumInfo::Ongoing(status) => Some(status.tally),
+			ReferendumInfo::Finished { .. } => None,
+		}
+	}
+
+	/// The votes of the account in the DAO.
+	pub fn votes_in(who: &T::AccountId, dao_id: T::DaoId) -> Vec<VoteInfoOf<T>> {
+		Self::votes_of(who).into_iter().filter(|vote| vote.dao_id == dao_id).collect()
+	}
+
+	/// Ensure that the DAO can make the call.
+	fn ensure_proposal_allowed(dao_id: T::DaoId, call: &CallOf<T>) -> DResult {
+		ensure!(
+			dao::Pallet::<T>::try_get_concrete_id(dao_id)?.contains(call.clone()),
+			dao::Error::<T>::InVailCall
+		);
+		dao::Pallet::<T>::ensure_call_allowed(dao_id, call)?;
+		dao::Pallet::<T>::ensure_can_dispatch(dao_id, call)?;
+		Ok(())
+	}
+
+	/// The threshold that the referendum needs to pass, if any.
+	fn threshold_of(
+		dao_id: T::DaoId,
+		index: ReferendumIndex,
+		call_id: T::CallId,
+	) -> Option<VoteThreshold> {
+		ReferendumThresholdOf::<T>::get(dao_id, index)
+			.or_else(|| VoteThresholdOf::<T>::get(dao_id, call_id))
+	}
+
+	/// Whether the account has votes in the ongoing referendums of the DAO.
+	fn is_voting(who: &T::AccountId, dao_id: T::DaoId) -> bool {
+		let now = Self::now();
+		Self::votes_of(who).iter().any(|vote| {
+			vote.dao_id == dao_id &&
+				matches!(
+					ReferendumInfoOf::<T>::get(dao_id, vote.referendum_index),
+					Some(ReferendumInfo::Ongoing(x)) if x.end > now
+				)
+		})
+	}
+
+	/// Add the delegated weight to, or remove it from, the votes of the delegate in the ongoing
+	/// referendums of the DAO.
+	fn update_votes_of_delegate(
+		dao_id: T::DaoId,
+		delegate: &T::AccountId,
+		weight: BalanceOf<T>,
+		add: bool,
+	) {
+		let now = Self::now();
+		let mut votes = VotesOf::<T>::get(delegate);
+		for vote in votes.iter_mut().filter(|vote| vote.dao_id == dao_id) {
+			ReferendumInfoOf::<T>::mutate(dao_id, vote.referendum_index, |info| {
+				if let Some(ReferendumInfo::Ongoing(x)) = info {
+					if x.end > now {
+						if add {
+							x.tally.add(&vote.opinion, weight);
+							vote.vote_weight = vote.vote_weight.saturating_add(weight);
+						} else {
+							x.tally.remove(&vote.opinion, weight);
+							vote.vote_weight = vote.vote_weight.saturating_sub(weight);
+						}
+					}
+				}
+			});
+		}
+		VotesOf::<T>::insert(delegate, votes);
+	}
+
+	/// Open a referendum for the best-backed public proposal if the launch period is over.
+	fn do_open_table(dao_id: T::DaoId) -> DResult {
+		let tag = LaunchTag::<T>::get(dao_id);
+		let now = Self::now();
+		let dao_start_time = dao::Pallet::<T>::try_get_dao(dao_id)?.start_block;
+		let launch_period = LaunchPeriod::<T>::get(dao_id);
+		let elapsed = now.saturating_sub(dao_start_time);
+		// (now - dao_start_time) / LaunchPeriod > tag
+		ensure!(
+			BlockNumberFor::<T>::from(tag)
+				.checked_mul(&launch_period)
+				.ok_or(Error::<T>::Overflow)? <
+				elapsed,
+			Error::<T>::NotTableTime
+		);
+		let index = Self::launch_public(dao_id)?;
+		let next_tag = elapsed
+			.checked_div(&launch_period)
+			.unwrap_or_else(Zero::zero)
+			.saturated_into::<u32>()
+			.saturating_add(1);
+		LaunchTag::<T>::insert(dao_id, next_tag);
+		Self::deposit_event(Event::<T>::StartTable(dao_id, index));
+
+		Ok(())
+	}
+
+	/// Enact the proposal of the referendum if it is approved after the delay.
+	fn do_enact_proposal(dao_id: T::DaoId, index: ReferendumIndex) -> DResult {
+		let now = Self::now();
+		let mut approved = false;
+		let info =
+			ReferendumInfoOf::<T>::get(dao_id, index).ok_or(Error::<T>::ReferendumNotExists)?;
+		match info {
+			ReferendumInfo::Ongoing(x) =>
+				if x.end > now {
+					return Err(Error::<T>::VoteNotEnd)?
+				} else if x.end.saturating_add(x.delay) > now {
+					return Err(Error::<T>::InDelayTime)?
+				} else {
+					{
+						let (call, _) = T::Preimages::peek(&x.proposal)
+							.map_err(|_| Error::<T>::PreimageMissing)?;
+						let call_id: T::CallId =
+							TryFrom::<<T as dao::Config>::Call>::try_from(call.clone())
+								.unwrap_or_default();
+
+						let passed = match Self::threshold_of(dao_id, index, call_id) {
+							Some(threshold) =>
+								threshold.approved(&x.tally, T::Pledge::electorate(&dao_id)),
+							None => x.tally.ayes >= x.tally.nays,
+						};
+						// A referendum that is blacklisted or without enough vote weight is
+						// rejected like one that doesn't pass, so that it is not left ongoing.
+						let enough =
+							x.tally.turnout() >= MinVoteWeightOf::<T>::get(dao_id, call_id);
+						let result = if Blacklist::<T>::contains_key(dao_id, x.proposal.hash()) {
+							Err(Error::<T>::ProposalBlacklisted.into())
+						} else if !enough {
+							Err(Error::<T>::VoteWeightTooLow.into())
+						} else if passed {
+							Self::ensure_proposal_allowed(dao_id, &call)?;
+							approved = true;
+							call.dispatch_bypass_filter(
+								frame_system::RawOrigin::Signed(
+									dao::Pallet::<T>::try_get_dao_account_id(dao_id)?,
+								)
+								.into(),
+							)
+							.map(|_| ())
+							.map_err(|e| e.error)
+						} else {
+							Err(Error::<T>::VoteEndButNotPass.into())
+						};
+						Self::deposit_event(Event::EnactProposal { dao_id, index, result });
+					}
+					T::Preimages::drop(&x.proposal);
+				},
+			_ => return Err(Error::<T>::ReferendumFinished)?,
+		}
+		ReferendumInfoOf::<T>::insert(
+			dao_id,
+			index,
+			ReferendumInfo::Finished { approved, end: now },
+		);
+		ReferendumThresholdOf::<T>::remove(dao_id, index);
+
+		Ok(())
+	}
+
+	/// The weight of the call of an ongoing referendum, zero if its preimage is missing.
+	fn proposal_weight(dao_id: T::DaoId, index: ReferendumIndex) -> Weight {
+		match ReferendumInfoOf::<T>::get(dao_id, index) {
+			Some(ReferendumInfo::Ongoing(x)) => T::Preimages::peek(&x.proposal)
+				.map(|(call, _)| call.get_dispatch_info().weight)
+				.unwrap_or_default(),
+			_ => Weight::zero(),
+		}
+	}
+
+	/// Schedule the next referendum of the DAO to be opened automatically, unless it is already
+	/// scheduled.
+	fn schedule_launch(dao_id: T::DaoId) {
+		if NextLaunch::<T>::contains_key(dao_id) {
+			return
+		}
+		if let Ok(dao) = dao::Pallet::<T>::try_get_dao(dao_id) {
+			// The first block at which `open_table` is allowed.
+			let when = BlockNumberFor::<T>::from(LaunchTag::<T>::get(dao_id))
+				.saturating_mul(LaunchPeriod::<T>::get(dao_id))
+				.saturating_add(dao.start_block)
+				.saturating_add(1u32.into())
+				.max(Self::now().saturating_add(1u32.into()));
+			NextLaunch::<T>::insert(dao_id, Self::schedule(when, Task::Launch(dao_id)));
+		}
+	}
+
+	/// Add the task to the agenda of the block, or of the first later block whose agenda is not
+	/// full, and return that block.
+	fn schedule(mut when: BlockNumberFor<T>, task: Task<T::DaoId>) -> BlockNumberFor<T> {
+		while Agenda::<T>::try_append(when, task.clone()).is_err() {
+			when = when.saturating_add(1u32.into());
+		}
+		when
+	}
+
 	fn launch_public(dao_id: T::DaoId) -> result::Result<ReferendumIndex, DispatchError> {
 		let mut public_props = Self::public_props(dao_id);
 		if let Some((winner_index, _)) = public_props
@@ -813,10 +1577,10 @@ impl<T: Config> Pallet<T> {
 			.max_by_key(|x| Self::backing_for(dao_id, (x.1).0).defensive_unwrap_or_else(Zero::zero))
 		{
 			let now = Self::now();
-			let (prop_index, _, proposal, _) = public_props.swap_remove(winner_index);
+			let (prop_index, proposal, _) = public_props.swap_remove(winner_index);
 			<PublicProps<T>>::insert(dao_id, public_props);
 
-			if <DepositOf<T>>::take(dao_id, prop_index).is_some() {
+			if Self::refund_deposits(dao_id, prop_index) {
 				return Ok(Self::inject_referendum(
 					dao_id,
 					now.saturating_add(VotingPeriod::<T>::get(dao_id)),
@@ -828,11 +1592,25 @@ impl<T: Config> Pallet<T> {
 		Err(Error::<T>::NoneWaiting)?
 	}
 
+	/// Return the deposits of the proposal to the proposer and the seconders.
+	fn refund_deposits(dao_id: T::DaoId, index: PropIndex) -> bool {
+		match DepositOf::<T>::take(dao_id, index) {
+			Some((depositors, deposit)) => {
+				for who in depositors {
+					<T as pallet::Config>::Currency::unreserve(&who, deposit);
+					Self::deposit_event(Event::<T>::Unreserved(who, deposit));
+				}
+				true
+			},
+			None => false,
+		}
+	}
+
 	fn inject_referendum(
 		dao_id: T::DaoId,
-		end: u32,
-		proposal: <T as dao::Config>::Call,
-		delay: u32,
+		end: BlockNumberFor<T>,
+		proposal: BoundedCallOf<T>,
+		delay: BlockNumberFor<T>,
 	) -> ReferendumIndex {
 		let ref_index = Self::referendum_count(dao_id);
 		ReferendumCount::<T>::insert(dao_id, ref_index + 1);
@@ -840,13 +1618,58 @@ impl<T: Config> Pallet<T> {
 
 		let item = ReferendumInfo::Ongoing(status);
 		<ReferendumInfoOf<T>>::insert(dao_id, ref_index, item);
+		Self::schedule(end.saturating_add(delay), Task::Enact(dao_id, ref_index));
 		ref_index
 	}
 
-	fn now() -> u32 {
-		// fixme
-		// frame_system::Pallet::<T>::current_block_number()
-		100
+	fn now() -> BlockNumberFor<T> {
+		frame_system::Pallet::<T>::block_number()
+	}
+}
+
+impl<T: Config> AfterDissolve<T::DaoId> for Pallet<T> {
+	fn do_something(dao_id: T::DaoId) {
+		for (index, proposal, _) in PublicProps::<T>::take(dao_id) {
+			T::Preimages::drop(&proposal);
+			Self::refund_deposits(dao_id, index);
+		}
+		for (_, info) in ReferendumInfoOf::<T>::drain_prefix(dao_id) {
+			if let ReferendumInfo::Ongoing(x) = info {
+				T::Preimages::drop(&x.proposal);
+			}
+		}
+		let _ = ReferendumThresholdOf::<T>::clear_prefix(dao_id, u32::MAX, None);
+		let _ = Blacklist::<T>::clear_prefix(dao_id, u32::MAX, None);
+		NextLaunch::<T>::remove(dao_id);
 	}
+}
 
+impl<T: Config> ApplyTemplate<T::AccountId, T::DaoId, DaoTemplateOf<T>> for Pallet<T> {
+	fn apply_template(
+		_creator: &T::AccountId,
+		dao_id: T::DaoId,
+		template: &DaoTemplateOf<T>,
+	) -> DResult {
+		if let Some(period) = template.launch_period {
+			LaunchPeriod::<T>::insert(dao_id, period);
+		}
+		if let Some(period) = template.voting_period {
+			VotingPeriod::<T>::insert(dao_id, period);
+		}
+		if let Some(period) = template.reserve_period {
+			ReservePeriod::<T>::insert(dao_id, period);
+		}
+		if let Some(period) = template.enactment_period {
+			EnactmentPeriod::<T>::insert(dao_id, period);
+		}
+		if let Some(min) = template.minimum_deposit {
+			// The template is in the currency of `dao`, which may not fit the one of this module.
+			let min = TryInto::<u128>::try_into(min)
+				.ok()
+				.and_then(|min| BalanceOf::<T>::try_from(min).ok())
+				.ok_or(Error::<T>::Overflow)?;
+			MinimumDeposit::<T>::insert(dao_id, min);
+		}
+		Ok(())
+	}
 }
@@ -0,0 +1,588 @@
+use super::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{DefensiveTruncateFrom, OnRuntimeUpgrade, StorageVersion},
+};
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+
+/// Remove a reserve of `amount` of the account, and return whether there is one.
+fn take_reserve<T: Config>(who: &T::AccountId, amount: BalanceOf<T>) -> bool {
+	ReserveOf::<T>::mutate_exists(who, |maybe_reserves| {
+		let reserves = match maybe_reserves {
+			Some(reserves) => reserves,
+			None => return false,
+		};
+		let found = match reserves.iter().position(|r| r.0 == amount) {
+			Some(position) => {
+				reserves.remove(position);
+				true
+			},
+			None => false,
+		};
+		if reserves.is_empty() {
+			*maybe_reserves = None;
+		}
+		found
+	})
+}
+
+/// Store all periods and block heights as `BlockNumberFor<T>` instead of `u32`.
+pub mod v1 {
+	use super::*;
+
+	/// `Tally` before the version 3.
+	#[derive(Encode, Decode, Default)]
+	pub struct OldTally<Balance> {
+		pub ayes: Balance,
+		pub nays: Balance,
+	}
+
+	/// `ReferendumStatus` before the version 3.
+	#[derive(Encode, Decode)]
+	pub struct OldReferendumStatus<BlockNumber, Call, Balance> {
+		pub end: BlockNumber,
+		pub proposal: Call,
+		pub delay: BlockNumber,
+		pub tally: OldTally<Balance>,
+	}
+
+	/// `ReferendumInfo` before the version 3.
+	#[derive(Encode, Decode)]
+	pub enum OldReferendumInfo<BlockNumber, Call, Balance> {
+		Ongoing(OldReferendumStatus<BlockNumber, Call, Balance>),
+		Finished { approved: bool, end: BlockNumber },
+	}
+
+	pub type OldReferendumInfoOf<T> =
+		OldReferendumInfo<u32, <T as dao::Config>::Call, BalanceOf<T>>;
+
+	pub type OldVoteInfoOf<T> = VoteInfo<
+		<T as dao::Config>::DaoId,
+		<T as dao::Config>::ConcreteId,
+		<T as Config>::Pledge,
+		u32,
+		BalanceOf<T>,
+		Opinion<BalanceOf<T>>,
+		ReferendumIndex,
+	>;
+
+	/// `ReferendumInfoOf` as it is stored before the version 3.
+	#[frame_support::storage_alias]
+	pub type ReferendumInfoOf<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Identity,
+		<T as dao::Config>::DaoId,
+		Identity,
+		ReferendumIndex,
+		OldReferendumInfo<BlockNumberFor<T>, <T as dao::Config>::Call, BalanceOf<T>>,
+	>;
+
+	/// `ReserveOf` as it is keyed before the version 2.
+	#[frame_support::storage_alias]
+	pub type ReserveOf<T: Config> = StorageMap<
+		Pallet<T>,
+		Identity,
+		<T as frame_system::Config>::AccountId,
+		Vec<(BalanceOf<T>, BlockNumberFor<T>)>,
+		ValueQuery,
+	>;
+
+	/// `VotesOf` as it is keyed before the version 2.
+	#[frame_support::storage_alias]
+	pub type VotesOf<T: Config> = StorageMap<
+		Pallet<T>,
+		Identity,
+		<T as frame_system::Config>::AccountId,
+		Vec<VoteInfoOf<T>>,
+		ValueQuery,
+	>;
+
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 0 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			let mut period = |_: T::DaoId, old: u32| -> Option<BlockNumberFor<T>> {
+				translated += 1;
+				Some(old.into())
+			};
+			LaunchPeriod::<T>::translate::<u32, _>(&mut period);
+			VotingPeriod::<T>::translate::<u32, _>(&mut period);
+			ReservePeriod::<T>::translate::<u32, _>(&mut period);
+			EnactmentPeriod::<T>::translate::<u32, _>(&mut period);
+			ReserveOf::<T>::translate::<Vec<(BalanceOf<T>, u32)>, _>(|_, old| {
+				translated += 1;
+				Some(old.into_iter().map(|(amount, end)| (amount, end.into())).collect())
+			});
+			ReferendumInfoOf::<T>::translate::<OldReferendumInfoOf<T>, _>(|_, _, old| {
+				translated += 1;
+				Some(match old {
+					OldReferendumInfo::Ongoing(x) =>
+						OldReferendumInfo::Ongoing(OldReferendumStatus {
+							end: x.end.into(),
+							proposal: x.proposal,
+							delay: x.delay.into(),
+							tally: x.tally,
+						}),
+					OldReferendumInfo::Finished { approved, end } =>
+						OldReferendumInfo::Finished { approved, end: end.into() },
+				})
+			});
+			VotesOf::<T>::translate::<Vec<OldVoteInfoOf<T>>, _>(|_, old| {
+				translated += 1;
+				Some(
+					old.into_iter()
+						.map(|vote| VoteInfo {
+							dao_id: vote.dao_id,
+							concrete_id: vote.concrete_id,
+							pledge: vote.pledge,
+							opinion: vote.opinion,
+							vote_weight: vote.vote_weight,
+							unlock_block: vote.unlock_block.into(),
+							referendum_index: vote.referendum_index,
+						})
+						.collect(),
+				)
+			});
+			StorageVersion::new(1).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let referendums = ReferendumInfoOf::<T>::iter_keys().count() as u32;
+			let voters = VotesOf::<T>::iter_keys().count() as u32;
+			Ok((referendums, voters).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let (referendums, voters) = <(u32, u32)>::decode(&mut &state[..])
+				.map_err(|_| "The state is not the counts.")?;
+			ensure!(
+				ReferendumInfoOf::<T>::iter().count() as u32 == referendums,
+				"Some referendums are not migrated."
+			);
+			ensure!(VotesOf::<T>::iter().count() as u32 == voters, "Some votes are not migrated.");
+			ensure!(StorageVersion::get::<Pallet<T>>() == 1, "The storage version is not 1.");
+			Ok(())
+		}
+	}
+}
+
+/// Key `ReserveOf` and `VotesOf` with `Blake2_128Concat` instead of `Identity`.
+pub mod v2 {
+	use super::*;
+
+	pub struct MigrateToV2<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 1 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let reserves = v1::ReserveOf::<T>::drain().collect::<Vec<_>>();
+			let votes = v1::VotesOf::<T>::drain().collect::<Vec<_>>();
+			let rekeyed = (reserves.len() + votes.len()) as u64;
+			for (who, mut reserve) in reserves {
+				// The reserves over the limit are returned at once instead of being lost.
+				let max = T::MaxReserves::get() as usize;
+				for (amount, _) in reserve.split_off(reserve.len().min(max)) {
+					frame_support::log::warn!(
+						"A reserve of {:?} over the limit is returned to {:?}.",
+						amount,
+						who
+					);
+					<T as pallet::Config>::Currency::unreserve(&who, amount);
+				}
+				ReserveOf::<T>::insert(who, BoundedVec::defensive_truncate_from(reserve));
+			}
+			let mut released = 0u64;
+			for (who, mut vote) in votes {
+				// The pledges of the votes over the limit are released at once, and their
+				// weights are taken out of the referendums that are still ongoing.
+				let max = T::MaxVotes::get() as usize;
+				for info in vote.split_off(vote.len().min(max)) {
+					released += 1;
+					v1::ReferendumInfoOf::<T>::mutate(info.dao_id, info.referendum_index, |r| {
+						if let Some(v1::OldReferendumInfo::Ongoing(x)) = r {
+							let (ayes, nays, _) = info.opinion.split(info.vote_weight);
+							x.tally.ayes = x.tally.ayes.saturating_sub(ayes);
+							x.tally.nays = x.tally.nays.saturating_sub(nays);
+						}
+					});
+					if info.pledge.vote_end_do(&who, &info.dao_id).is_err() {
+						frame_support::log::warn!(
+							"The pledge of a vote over the limit of {:?} can not be released.",
+							who
+						);
+					}
+				}
+				VotesOf::<T>::insert(who, BoundedVec::defensive_truncate_from(vote));
+			}
+			StorageVersion::new(2).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(rekeyed + released + 1, rekeyed * 2 + released + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let reserves = v1::ReserveOf::<T>::iter_keys().count() as u32;
+			let voters = v1::VotesOf::<T>::iter_keys().count() as u32;
+			Ok((reserves, voters).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let (reserves, voters) = <(u32, u32)>::decode(&mut &state[..])
+				.map_err(|_| "The state is not the counts.")?;
+			ensure!(ReserveOf::<T>::iter().count() as u32 == reserves, "Some reserves are lost.");
+			ensure!(VotesOf::<T>::iter().count() as u32 == voters, "Some votes are lost.");
+			ensure!(StorageVersion::get::<Pallet<T>>() == 2, "The storage version is not 2.");
+			Ok(())
+		}
+	}
+}
+
+/// Count the abstentions in `Tally`.
+pub mod v3 {
+	use super::*;
+
+	/// `PublicProps` as it is stored before the version 4.
+	#[frame_support::storage_alias]
+	pub type PublicProps<T: Config> = StorageMap<
+		Pallet<T>,
+		Identity,
+		<T as dao::Config>::DaoId,
+		Vec<(
+			PropIndex,
+			<T as frame_system::Config>::Hash,
+			<T as dao::Config>::Call,
+			<T as frame_system::Config>::AccountId,
+		)>,
+		ValueQuery,
+	>;
+
+	/// `ReferendumInfoOf` as it is stored before the version 4.
+	#[frame_support::storage_alias]
+	pub type ReferendumInfoOf<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Identity,
+		<T as dao::Config>::DaoId,
+		Identity,
+		ReferendumIndex,
+		ReferendumInfo<BlockNumberFor<T>, <T as dao::Config>::Call, BalanceOf<T>>,
+	>;
+
+	pub struct MigrateToV3<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 2 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			ReferendumInfoOf::<T>::translate::<
+				v1::OldReferendumInfo<BlockNumberFor<T>, <T as dao::Config>::Call, BalanceOf<T>>,
+				_,
+			>(|_, _, old| {
+				translated += 1;
+				Some(match old {
+					v1::OldReferendumInfo::Ongoing(x) =>
+						ReferendumInfo::Ongoing(ReferendumStatus {
+							end: x.end,
+							proposal: x.proposal,
+							delay: x.delay,
+							tally: Tally {
+								ayes: x.tally.ayes,
+								nays: x.tally.nays,
+								abstentions: Zero::zero(),
+							},
+						}),
+					v1::OldReferendumInfo::Finished { approved, end } =>
+						ReferendumInfo::Finished { approved, end },
+				})
+			});
+			StorageVersion::new(3).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			Ok((v1::ReferendumInfoOf::<T>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let count = u32::decode(&mut &state[..]).map_err(|_| "The state is not a count.")?;
+			ensure!(
+				ReferendumInfoOf::<T>::iter().count() as u32 == count,
+				"Some referendums are not migrated."
+			);
+			ensure!(StorageVersion::get::<Pallet<T>>() == 3, "The storage version is not 3.");
+			Ok(())
+		}
+	}
+}
+
+/// Store the calls of the public proposals and the referendums in the preimages.
+pub mod v4 {
+	use super::*;
+
+	/// Store the call in the preimages and request it, or drop it if it is too long.
+	fn bound<T: Config>(call: <T as dao::Config>::Call) -> Option<BoundedCallOf<T>> {
+		let proposal = T::Preimages::bound(call).defensive().ok()?;
+		T::Preimages::hold(&proposal);
+		Some(proposal)
+	}
+
+	/// Return the deposits of a public proposal that is dropped. The seconds are only returned if
+	/// they are not released by `unlock` yet.
+	fn refund<T: Config>(dao_id: T::DaoId, index: PropIndex) {
+		frame_support::log::warn!(
+			"The public proposal {:?} of the DAO {:?} is too long and is dropped.",
+			index,
+			dao_id
+		);
+		if let Some((depositors, deposit)) = DepositOf::<T>::take(dao_id, index) {
+			for (i, who) in depositors.into_iter().enumerate() {
+				if i == 0 || take_reserve::<T>(&who, deposit) {
+					<T as pallet::Config>::Currency::unreserve(&who, deposit);
+				}
+			}
+		}
+	}
+
+	pub struct MigrateToV4<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 3 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			PublicProps::<T>::translate::<
+				Vec<(
+					PropIndex,
+					<T as frame_system::Config>::Hash,
+					<T as dao::Config>::Call,
+					<T as frame_system::Config>::AccountId,
+				)>,
+				_,
+			>(|dao_id, old| {
+				translated += 1;
+				Some(BoundedVec::defensive_truncate_from(
+					old.into_iter()
+						.filter_map(|(index, _, call, who)| match bound::<T>(call) {
+							Some(proposal) => Some((index, proposal, who)),
+							None => {
+								refund::<T>(dao_id, index);
+								None
+							},
+						})
+						.collect(),
+				))
+			});
+			ReferendumInfoOf::<T>::translate::<
+				ReferendumInfo<BlockNumberFor<T>, <T as dao::Config>::Call, BalanceOf<T>>,
+				_,
+			>(|dao_id, index, old| {
+				translated += 1;
+				Some(match old {
+					ReferendumInfo::Ongoing(x) => match bound::<T>(x.proposal) {
+						Some(proposal) => ReferendumInfo::Ongoing(ReferendumStatus {
+							end: x.end,
+							proposal,
+							delay: x.delay,
+							tally: x.tally,
+						}),
+						// The votes are still released by `unlock`.
+						None => {
+							frame_support::log::warn!(
+								"The referendum {:?} of the DAO {:?} is too long and is rejected.",
+								index,
+								dao_id
+							);
+							ReferendumInfo::Finished { approved: false, end: x.end }
+						},
+					},
+					ReferendumInfo::Finished { approved, end } =>
+						ReferendumInfo::Finished { approved, end },
+				})
+			});
+			StorageVersion::new(4).put::<Pallet<T>>();
+			// Each call is also noted and requested in the preimages.
+			T::DbWeight::get().reads_writes(translated * 3 + 1, translated * 3 + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let proposals =
+				v3::PublicProps::<T>::iter_values().map(|props| props.len() as u32).sum::<u32>();
+			let referendums = v3::ReferendumInfoOf::<T>::iter_keys().count() as u32;
+			Ok((proposals, referendums).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let (proposals, referendums) = <(u32, u32)>::decode(&mut &state[..])
+				.map_err(|_| "The state is not the counts.")?;
+			ensure!(
+				PublicProps::<T>::iter_values().map(|props| props.len() as u32).sum::<u32>() ==
+					proposals,
+				"Some proposals are not migrated."
+			);
+			ensure!(
+				ReferendumInfoOf::<T>::iter().count() as u32 == referendums,
+				"Some referendums are not migrated."
+			);
+			ensure!(StorageVersion::get::<Pallet<T>>() == 4, "The storage version is not 4.");
+			Ok(())
+		}
+	}
+}
+
+/// Tie the deposits of the seconds to the proposals.
+///
+/// The seconds of the public proposals are tracked by `DepositOf` and returned when the proposals
+/// are launched, so they are removed from `ReserveOf`. A second without a reserve of its amount
+/// has already been released by `unlock`, so it is removed from `DepositOf`. The other reserves
+/// are still released by `unlock`.
+pub mod v5 {
+	use super::*;
+
+	/// The amount reserved by each second of the public proposals.
+	#[cfg(feature = "try-runtime")]
+	fn seconds<T: Config>() -> Vec<(<T as frame_system::Config>::AccountId, BalanceOf<T>)> {
+		DepositOf::<T>::iter_values()
+			.flat_map(|(depositors, deposit)| {
+				depositors.into_iter().skip(1).map(move |who| (who, deposit))
+			})
+			.collect()
+	}
+
+	pub struct MigrateToV5<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV5<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 4 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let deposits = DepositOf::<T>::iter().collect::<Vec<_>>();
+			let mut moved = 0u64;
+			for (dao_id, index, (depositors, deposit)) in deposits {
+				let count = depositors.len();
+				let mut kept = Vec::with_capacity(count);
+				for (i, who) in depositors.into_iter().enumerate() {
+					// The proposer is not in `ReserveOf`.
+					if i == 0 || take_reserve::<T>(&who, deposit) {
+						kept.push(who);
+					} else {
+						frame_support::log::warn!(
+							"A second of the proposal {:?} of the DAO {:?} is already unreserved.",
+							index,
+							dao_id
+						);
+					}
+				}
+				moved += count.saturating_sub(1) as u64;
+				if kept.len() < count {
+					DepositOf::<T>::insert(
+						dao_id,
+						index,
+						(BoundedVec::truncate_from(kept), deposit),
+					);
+				}
+			}
+			StorageVersion::new(5).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(moved * 2 + 1, moved * 2 + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let reserves =
+				ReserveOf::<T>::iter_values().map(|reserves| reserves.len() as u32).sum::<u32>();
+			Ok(reserves.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let reserves = u32::decode(&mut &state[..]).map_err(|_| "The state is not a count.")?;
+			ensure!(
+				ReserveOf::<T>::iter_values().map(|reserves| reserves.len() as u32).sum::<u32>() +
+					seconds::<T>().len() as u32 ==
+					reserves,
+				"Some reserves of the seconds are not removed."
+			);
+			ensure!(StorageVersion::get::<Pallet<T>>() == 5, "The storage version is not 5.");
+			Ok(())
+		}
+	}
+}
+
+/// Schedule the referendums and the public proposals that exist before they are done
+/// automatically.
+pub mod v6 {
+	use super::*;
+
+	fn ongoing<T: Config>() -> Vec<(T::DaoId, ReferendumIndex, BlockNumberFor<T>)> {
+		ReferendumInfoOf::<T>::iter()
+			.filter_map(|(dao_id, index, info)| match info {
+				ReferendumInfo::Ongoing(x) => Some((dao_id, index, x.end.saturating_add(x.delay))),
+				_ => None,
+			})
+			.collect()
+	}
+
+	pub struct MigrateToV6<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV6<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 5 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let next = frame_system::Pallet::<T>::block_number().saturating_add(1u32.into());
+			let referendums = ongoing::<T>();
+			let mut scheduled = referendums.len() as u64;
+			for (dao_id, index, when) in referendums {
+				Pallet::<T>::schedule(when.max(next), Task::Enact(dao_id, index));
+			}
+			for (dao_id, props) in PublicProps::<T>::iter() {
+				if !props.is_empty() {
+					Pallet::<T>::schedule_launch(dao_id);
+					scheduled += 1;
+				}
+			}
+			StorageVersion::new(6).put::<Pallet<T>>();
+			let read = ReferendumInfoOf::<T>::iter_keys().count() as u64 +
+				PublicProps::<T>::iter_keys().count() as u64;
+			T::DbWeight::get().reads_writes(read + scheduled * 3 + 1, scheduled * 2 + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			Ok((ongoing::<T>().len() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let count = u32::decode(&mut &state[..]).map_err(|_| "The state is not a count.")?;
+			let enacts = Agenda::<T>::iter_values()
+				.flatten()
+				.filter(|task| matches!(task, Task::Enact(..)))
+				.count() as u32;
+			ensure!(enacts >= count, "Some referendums are not scheduled.");
+			ensure!(StorageVersion::get::<Pallet<T>>() == 6, "The storage version is not 6.");
+			Ok(())
+		}
+	}
+}
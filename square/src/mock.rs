@@ -4,11 +4,13 @@ use crate as square;
 use crate::Pledge;
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
+	parameter_types,
 	traits::{ConstU16, ConstU32, ConstU64},
+	weights::Weight,
 	RuntimeDebug,
 };
 use frame_system;
-use primitives::{ids::Nft, traits::BaseCallFilter};
+use primitives::{ids::Nft, mock::Collections, traits::BaseCallFilter, types::CallId};
 use scale_info::TypeInfo;
 use sp_core::H256;
 use sp_runtime::{
@@ -30,6 +32,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Preimage: pallet_preimage::{Pallet, Call, Storage, Event<T>},
 		DAO: dao::{ Pallet, Call, Event<T>, Storage },
 		Sudo: sudo::{ Pallet, Call, Event<T>, Storage },
 		Square: square::{ Pallet, Call, Event<T>, Storage },
@@ -63,14 +66,7 @@ impl frame_system::Config for Test {
 	type MaxConsumers = ConstU32<16>;
 }
 
-impl TryFrom<Call> for u64 {
-	type Error = ();
-	fn try_from(call: Call) -> Result<Self, Self::Error> {
-		match call {
-			_ => Ok(0u64),
-		}
-	}
-}
+primitives::impl_call_id!(Call);
 impl BaseCallFilter<Call> for Nft<u64> {
 	fn contains(&self, call: Call) -> bool {
 		true
@@ -89,13 +85,31 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = ();
 }
 
+impl pallet_preimage::Config for Test {
+	type Event = Event;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type ManagerOrigin = frame_system::EnsureRoot<u64>;
+	type BaseDeposit = ConstU64<2>;
+	type ByteDeposit = ConstU64<1>;
+}
+
 impl dao::Config for Test {
 	type Event = Event;
 	type Call = Call;
-	type CallId = u64;
+	type CallId = CallId;
 	type DaoId = u64;
 	type ConcreteId = Nft<u64>;
+	type Inspector = Collections;
 	type AfterCreate = ();
+	type AfterDissolve = (Square, Sudo);
+	type AfterCreatorChanged = ();
+	type ApplyTemplate = (Square, Sudo);
+	type MaxTemplateOrigins = ConstU32<10>;
+	type Currency = Balances;
+	type CreationDeposit = ConstU64<0>;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type StringLimit = ConstU32<50>;
 	type WeightInfo = ();
 }
 
@@ -117,6 +131,14 @@ impl Pledge<u64, u64, u64, (), u64, DispatchError> for Vote {
 	fn vote_end_do(&self, _who: &u64, _dao_id: &u64) -> Result<(), DispatchError> {
 		Ok(())
 	}
+
+	fn electorate(_dao_id: &u64) -> u64 {
+		1000u64
+	}
+}
+
+parameter_types! {
+	pub MaxScheduledWeight: Weight = Weight::from_all(1_000_000_000);
 }
 
 impl square::Config for Test {
@@ -124,6 +146,13 @@ impl square::Config for Test {
 	type Pledge = Vote;
 	type Conviction = ();
 	type Currency = Balances;
+	type Preimages = Preimage;
+	type MaxProposals = ConstU32<100>;
+	type MaxDeposits = ConstU32<3>;
+	type MaxReserves = ConstU32<100>;
+	type MaxVotes = ConstU32<100>;
+	type MaxScheduledPerBlock = ConstU32<10>;
+	type MaxScheduledWeight = MaxScheduledWeight;
 	type WeightInfo = ();
 }
 
@@ -141,3 +170,123 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	.unwrap();
 	t.into()
 }
+
+/// A runtime that votes with `LockedPledge`, to test the locks together with the calls.
+pub mod locked {
+	use super::*;
+	use crate::{Conviction, LockedPledge};
+
+	type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<LockedTest>;
+	pub type Block = frame_system::mocking::MockBlock<LockedTest>;
+
+	frame_support::construct_runtime!(
+		pub enum LockedTest where
+			Block = Block,
+			NodeBlock = Block,
+			UncheckedExtrinsic = UncheckedExtrinsic,
+		{
+			System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+			Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+			Preimage: pallet_preimage::{Pallet, Call, Storage, Event<T>},
+			DAO: dao::{ Pallet, Call, Event<T>, Storage },
+			Square: square::{ Pallet, Call, Event<T>, Storage },
+		}
+	);
+
+	impl frame_system::Config for LockedTest {
+		type BaseCallFilter = frame_support::traits::Everything;
+		type BlockWeights = ();
+		type BlockLength = ();
+		type Origin = Origin;
+		type Call = Call;
+		type Index = u64;
+		type BlockNumber = u64;
+		type Hash = H256;
+		type Hashing = BlakeTwo256;
+		type AccountId = u64;
+		type Lookup = IdentityLookup<Self::AccountId>;
+		type Header = Header;
+		type Event = Event;
+		type BlockHashCount = ConstU64<250>;
+		type DbWeight = ();
+		type Version = ();
+		type PalletInfo = PalletInfo;
+		type AccountData = pallet_balances::AccountData<u64>;
+		type OnNewAccount = ();
+		type OnKilledAccount = ();
+		type SystemWeightInfo = ();
+		type SS58Prefix = ConstU16<42>;
+		type OnSetCode = ();
+		type MaxConsumers = ConstU32<16>;
+	}
+
+	primitives::impl_call_id!(Call);
+	impl BaseCallFilter<Call> for Nft<u64> {
+		fn contains(&self, call: Call) -> bool {
+			true
+		}
+	}
+
+	impl pallet_balances::Config for LockedTest {
+		type Balance = u64;
+		type Event = Event;
+		type DustRemoval = ();
+		type ExistentialDeposit = ConstU64<1>;
+		type AccountStore = System;
+		type MaxLocks = ConstU32<10>;
+		type MaxReserves = ();
+		type ReserveIdentifier = [u8; 8];
+		type WeightInfo = ();
+	}
+
+	impl pallet_preimage::Config for LockedTest {
+		type Event = Event;
+		type WeightInfo = ();
+		type Currency = Balances;
+		type ManagerOrigin = frame_system::EnsureRoot<u64>;
+		type BaseDeposit = ConstU64<2>;
+		type ByteDeposit = ConstU64<1>;
+	}
+
+	impl dao::Config for LockedTest {
+		type Event = Event;
+		type Call = Call;
+		type CallId = CallId;
+		type DaoId = u64;
+		type ConcreteId = Nft<u64>;
+		type Inspector = Collections;
+		type AfterCreate = ();
+		type AfterDissolve = Square;
+		type AfterCreatorChanged = ();
+		type ApplyTemplate = Square;
+		type MaxTemplateOrigins = ConstU32<10>;
+		type Currency = Balances;
+		type CreationDeposit = ConstU64<0>;
+		type ForceOrigin = frame_system::EnsureRoot<u64>;
+		type StringLimit = ConstU32<50>;
+		type WeightInfo = ();
+	}
+
+	impl square::Config for LockedTest {
+		type Event = Event;
+		type Pledge = LockedPledge<LockedTest>;
+		type Conviction = Conviction;
+		type Currency = Balances;
+		type Preimages = Preimage;
+		type MaxProposals = ConstU32<100>;
+		type MaxDeposits = ConstU32<3>;
+		type MaxReserves = ConstU32<100>;
+		type MaxVotes = ConstU32<100>;
+		type MaxScheduledPerBlock = ConstU32<10>;
+		type MaxScheduledWeight = MaxScheduledWeight;
+		type WeightInfo = ();
+	}
+
+	pub fn new_test_ext() -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<LockedTest>().unwrap();
+		pallet_balances::GenesisConfig::<LockedTest> { balances: vec![(1, 1000), (2, 1000)] }
+			.assimilate_storage(&mut t)
+			.unwrap();
+		t.into()
+	}
+}
@@ -3,14 +3,21 @@
 
 use super::*;
 use crate::mock::{Call, Origin, *};
-use frame_support::assert_ok;
-use primitives::ids::Nft;
-use sp_runtime::traits::BlakeTwo256;
+use frame_support::{
+	assert_noop, assert_ok,
+	storage::unhashed,
+	traits::{Hooks, OnRuntimeUpgrade, StorageVersion},
+};
+use primitives::{ids::Nft, types::CallId};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	BuildStorage,
+};
 
 pub const ALICE: u64 = 1;
 
 pub fn create_dao() {
-	dao::Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 4]).unwrap();
+	dao::Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 4], None).unwrap();
 }
 
 pub fn set_sudo() {
@@ -82,6 +89,27 @@ pub fn sudo_set_xxx() {
 	));
 }
 
+fn set_min_vote_weight_id() -> CallId {
+	CallId::from_call(&Call::Square(crate::Call::set_min_vote_weight_for_every_call {
+		dao_id: 0u64,
+		call_id: CallId::default(),
+		min_vote_weight: 100u64,
+	}))
+	.unwrap()
+}
+
+pub fn bounded(call: Call) -> BoundedCallOf<Test> {
+	Preimage::bound(call).unwrap()
+}
+
+pub fn run_to_block(n: u64) {
+	while frame_system::Pallet::<Test>::block_number() < n {
+		let next = frame_system::Pallet::<Test>::block_number() + 1;
+		frame_system::Pallet::<Test>::set_block_number(next);
+		crate::Pallet::<Test>::on_initialize(next);
+	}
+}
+
 pub fn propose() {
 	create_dao();
 	frame_system::Pallet::<Test>::set_block_number(10000);
@@ -89,13 +117,13 @@ pub fn propose() {
 	frame_system::Pallet::<Test>::set_block_number(0);
 	let proposal = Call::Square(crate::Call::set_min_vote_weight_for_every_call {
 		dao_id: 0u64,
-		call_id: 0u64,
+		call_id: set_min_vote_weight_id(),
 		min_vote_weight: 100u64,
 	});
 	assert_ok!(crate::Pallet::<Test>::propose(
 		Origin::signed(ALICE),
 		0u64,
-		Box::new(proposal),
+		bounded(proposal),
 		0u64
 	));
 }
@@ -123,7 +151,7 @@ pub fn vote() {
 		Opinion::AYES,
 	));
 	assert_ok!(crate::Pallet::<Test>::vote_for_referendum(
-		Origin::signed(ALICE),
+		Origin::signed(2u64),
 		0u64,
 		0u32,
 		Vote(100u64),
@@ -131,7 +159,7 @@ pub fn vote() {
 		Opinion::AYES,
 	));
 	assert_ok!(crate::Pallet::<Test>::vote_for_referendum(
-		Origin::signed(ALICE),
+		Origin::signed(3u64),
 		0u64,
 		0u32,
 		Vote(100u64),
@@ -159,10 +187,6 @@ pub fn enact() {
 	);
 	assert!(crate::Pallet::<Test>::enact_proposal(Origin::signed(ALICE), 0u64, 0u32).is_err());
 	frame_system::Pallet::<Test>::set_block_number(20000);
-	let ole_min_weight = MinVoteWeightOf::<Test>::get(0u64, 0u64);
-	MinVoteWeightOf::<Test>::insert(0u64, 0u64, 10000000000);
-	assert!(crate::Pallet::<Test>::enact_proposal(Origin::signed(ALICE), 0u64, 0u32).is_err());
-	MinVoteWeightOf::<Test>::insert(0u64, 0u64, ole_min_weight);
 	assert_ok!(crate::Pallet::<Test>::enact_proposal(Origin::signed(ALICE), 0u64, 0u32));
 	assert!(crate::Pallet::<Test>::enact_proposal(Origin::signed(ALICE), 0u64, 0u32).is_err());
 	assert!(crate::Pallet::<Test>::vote_for_referendum(
@@ -189,6 +213,19 @@ pub fn second_should_work() {
 	new_test_ext().execute_with(|| second());
 }
 
+#[test]
+pub fn too_many_deposits_should_fail() {
+	new_test_ext().execute_with(|| {
+		second();
+		assert_ok!(crate::Pallet::<Test>::second(Origin::signed(3u64), 0u64, 0u32));
+		assert_noop!(
+			crate::Pallet::<Test>::second(Origin::signed(10u64), 0u64, 0u32),
+			Error::<Test>::TooManyDeposits
+		);
+		assert_eq!(DepositOf::<Test>::get(0u64, 0u32).unwrap().0, vec![ALICE, 2u64, 3u64]);
+	});
+}
+
 #[test]
 pub fn vote_should_work() {
 	new_test_ext().execute_with(|| {
@@ -196,6 +233,37 @@ pub fn vote_should_work() {
 	});
 }
 
+#[test]
+pub fn vote_again_should_replace_the_vote() {
+	new_test_ext().execute_with(|| {
+		vote();
+		let tally = || crate::Pallet::<Test>::referendum_tally(0u64, 0u32).unwrap();
+		assert_ok!(crate::Pallet::<Test>::vote_for_referendum(
+			Origin::signed(ALICE),
+			0u64,
+			0u32,
+			Vote(100u64),
+			(),
+			Opinion::NAYS,
+		));
+		assert_eq!((tally().ayes, tally().nays), (100u64, 200u64));
+		assert_ok!(crate::Pallet::<Test>::vote_for_referendum(
+			Origin::signed(ALICE),
+			0u64,
+			0u32,
+			Vote(100u64),
+			(),
+			Opinion::NAYS,
+		));
+		assert_eq!((tally().ayes, tally().nays), (100u64, 200u64));
+		assert_eq!(crate::Pallet::<Test>::votes_in(&ALICE, 0u64).len(), 1);
+
+		assert_ok!(crate::Pallet::<Test>::cancel_vote(Origin::signed(ALICE), 0u64, 0u32));
+		assert_eq!((tally().ayes, tally().nays), (100u64, 100u64));
+		assert!(crate::Pallet::<Test>::votes_in(&ALICE, 0u64).is_empty());
+	});
+}
+
 #[test]
 pub fn cancel_vote_should_work() {
 	new_test_ext().execute_with(|| {
@@ -203,7 +271,16 @@ pub fn cancel_vote_should_work() {
 		frame_system::Pallet::<Test>::set_block_number(20000);
 		assert!(crate::Pallet::<Test>::cancel_vote(Origin::signed(ALICE), 0u64, 0u32).is_err());
 		frame_system::Pallet::<Test>::set_block_number(10000);
+		// A vote for the referendum with the same index in another DAO is kept.
+		VotesOf::<Test>::mutate(ALICE, |votes| {
+			let mut other = votes[0].clone();
+			other.dao_id = 1u64;
+			votes.try_push(other).unwrap();
+		});
 		assert_ok!(crate::Pallet::<Test>::cancel_vote(Origin::signed(ALICE), 0u64, 0u32));
+		let votes = VotesOf::<Test>::get(ALICE);
+		assert_eq!(votes.len(), 1);
+		assert_eq!(votes[0].dao_id, 1u64);
 	});
 }
 
@@ -228,3 +305,937 @@ pub fn sudo_set_xxx_should_work() {
 		sudo_set_xxx();
 	});
 }
+
+#[test]
+pub fn dissolve_dao_should_clean_up() {
+	new_test_ext().execute_with(|| {
+		vote();
+		let proposal = Call::DAO(dao::Call::dao_remark { dao_id: 0u64, remark: vec![1; 10] });
+		assert_ok!(crate::Pallet::<Test>::propose(
+			Origin::signed(ALICE),
+			0u64,
+			bounded(proposal.clone()),
+			5u64
+		));
+		assert_eq!(Balances::reserved_balance(ALICE), 5u64);
+
+		let dao_account = dao::Daos::<Test>::get(0u64).unwrap().dao_account_id;
+		assert_ok!(dao::Pallet::<Test>::dissolve_dao(Origin::signed(dao_account), 0u64));
+		assert!(PublicProps::<Test>::get(0u64).is_empty());
+		assert!(DepositOf::<Test>::get(0u64, 1u32).is_none());
+		assert!(ReferendumInfoOf::<Test>::get(0u64, 0u32).is_none());
+		assert_eq!(Balances::reserved_balance(ALICE), 0u64);
+		assert!(crate::Pallet::<Test>::propose(
+			Origin::signed(ALICE),
+			0u64,
+			bounded(proposal),
+			0u64
+		)
+		.is_err());
+
+		assert_ok!(crate::Pallet::<Test>::unlock(Origin::signed(ALICE)));
+		assert!(VotesOf::<Test>::get(ALICE).is_empty());
+	});
+}
+
+#[test]
+pub fn create_dao_from_template_should_work() {
+	new_test_ext().execute_with(|| {
+		let template = dao::DaoTemplate {
+			launch_period: Some(10u64),
+			voting_period: Some(20u64),
+			reserve_period: None,
+			enactment_period: None,
+			minimum_deposit: Some(5u64),
+			motion_duration: None,
+			ensure_origins: Default::default(),
+			emergency_pledge: None,
+			sudo: true,
+		};
+		assert_ok!(dao::Pallet::<Test>::register_template(Origin::root(), template));
+		assert_ok!(dao::Pallet::<Test>::create_dao(
+			Origin::signed(ALICE),
+			Nft(0u64),
+			vec![1; 4],
+			Some(0u32)
+		));
+		assert_eq!(crate::LaunchPeriod::<Test>::get(0u64), 10u64);
+		assert_eq!(crate::VotingPeriod::<Test>::get(0u64), 20u64);
+		assert_eq!(crate::ReservePeriod::<Test>::get(0u64), 900u64);
+		assert_eq!(crate::MinimumDeposit::<Test>::get(0u64), 5u64);
+		assert_eq!(sudo::Account::<Test>::get(0u64), Some(ALICE));
+	});
+}
+
+#[test]
+pub fn query_should_work() {
+	new_test_ext().execute_with(|| {
+		propose();
+		let proposals = crate::Pallet::<Test>::open_proposals(0u64);
+		assert_eq!(proposals.len(), 1);
+		assert_eq!((proposals[0].0, proposals[0].2), (0u32, ALICE));
+	});
+	new_test_ext().execute_with(|| {
+		vote();
+		assert!(crate::Pallet::<Test>::open_proposals(0u64).is_empty());
+		let tally = crate::Pallet::<Test>::referendum_tally(0u64, 0u32).unwrap();
+		assert_eq!((tally.ayes, tally.nays), (200u64, 100u64));
+		assert!(crate::Pallet::<Test>::referendum_tally(0u64, 1u32).is_none());
+		assert_eq!(crate::Pallet::<Test>::votes_in(&ALICE, 0u64).len(), 1);
+		assert!(crate::Pallet::<Test>::votes_in(&ALICE, 1u64).is_empty());
+	});
+}
+
+#[test]
+pub fn genesis_config_should_work() {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	crate::GenesisConfig::<Test> {
+		min_vote_weights: vec![(0u64, set_min_vote_weight_id(), 100u64)],
+		launch_periods: vec![(0u64, 10u64)],
+		voting_periods: vec![(0u64, 20u64)],
+		reserve_periods: vec![],
+		enactment_periods: vec![(0u64, 30u64)],
+		vote_locking_periods: vec![(0u64, 40u64)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		assert_eq!(MinVoteWeightOf::<Test>::get(0u64, set_min_vote_weight_id()), 100u64);
+		assert_eq!(LaunchPeriod::<Test>::get(0u64), 10u64);
+		assert_eq!(VotingPeriod::<Test>::get(0u64), 20u64);
+		assert_eq!(ReservePeriod::<Test>::get(0u64), 900u64);
+		assert_eq!(EnactmentPeriod::<Test>::get(0u64), 30u64);
+		assert_eq!(VoteLockingPeriod::<Test>::get(0u64), 40u64);
+	});
+}
+
+#[test]
+pub fn migrate_to_v1_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<Pallet<Test>>();
+		unhashed::put(&VotingPeriod::<Test>::hashed_key_for(0u64), &20u32);
+		unhashed::put(
+			&migrations::v1::ReserveOf::<Test>::hashed_key_for(ALICE),
+			&vec![(10u64, 30u32)],
+		);
+		let proposal = Call::DAO(dao::Call::dao_remark { dao_id: 0u64, remark: vec![1; 10] });
+		unhashed::put(
+			&ReferendumInfoOf::<Test>::hashed_key_for(0u64, 0u32),
+			&migrations::v1::OldReferendumInfo::Ongoing(migrations::v1::OldReferendumStatus {
+				end: 40u32,
+				proposal,
+				delay: 50u32,
+				tally: migrations::v1::OldTally::<u64>::default(),
+			}),
+		);
+		unhashed::put(
+			&migrations::v1::VotesOf::<Test>::hashed_key_for(ALICE),
+			&vec![VoteInfo {
+				dao_id: 0u64,
+				concrete_id: Nft(0u64),
+				pledge: Vote(100u64),
+				opinion: Opinion::AYES,
+				vote_weight: 100u64,
+				unlock_block: 60u32,
+				referendum_index: 0u32,
+			}],
+		);
+
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<Pallet<Test>>(), 1);
+		assert_eq!(VotingPeriod::<Test>::get(0u64), 20u64);
+		assert_eq!(LaunchPeriod::<Test>::get(0u64), 900u64);
+		assert_eq!(migrations::v1::ReserveOf::<Test>::get(ALICE), vec![(10u64, 30u64)]);
+		match migrations::v1::ReferendumInfoOf::<Test>::get(0u64, 0u32) {
+			Some(migrations::v1::OldReferendumInfo::Ongoing(x)) =>
+				assert_eq!((x.end, x.delay), (40u64, 50u64)),
+			_ => panic!("The referendum is not migrated."),
+		}
+		assert_eq!(migrations::v1::VotesOf::<Test>::get(ALICE)[0].unlock_block, 60u64);
+	});
+}
+
+#[test]
+pub fn migrate_to_v2_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<Pallet<Test>>();
+		migrations::v1::ReserveOf::<Test>::insert(ALICE, vec![(10u64, 30u64)]);
+		migrations::v1::VotesOf::<Test>::insert(
+			ALICE,
+			vec![VoteInfo {
+				dao_id: 0u64,
+				concrete_id: Nft(0u64),
+				pledge: Vote(100u64),
+				opinion: Opinion::AYES,
+				vote_weight: 100u64,
+				unlock_block: 60u64,
+				referendum_index: 0u32,
+			}],
+		);
+		assert!(ReserveOf::<Test>::get(ALICE).is_empty());
+
+		migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<Pallet<Test>>(), 2);
+		assert_eq!(ReserveOf::<Test>::get(ALICE), vec![(10u64, 30u64)]);
+		assert_eq!(VotesOf::<Test>::get(ALICE)[0].unlock_block, 60u64);
+		assert!(!migrations::v1::ReserveOf::<Test>::contains_key(ALICE));
+		assert!(!migrations::v1::VotesOf::<Test>::contains_key(ALICE));
+
+		// The reserves over the limit are returned.
+		StorageVersion::new(1).put::<Pallet<Test>>();
+		assert_ok!(Balances::reserve(&ALICE, 5u64));
+		let mut reserves = vec![(0u64, 30u64); 100];
+		reserves.push((5u64, 30u64));
+		migrations::v1::ReserveOf::<Test>::insert(ALICE, reserves);
+		migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(ReserveOf::<Test>::get(ALICE).len(), 100);
+		assert_eq!(Balances::reserved_balance(ALICE), 0u64);
+
+		// The weights of the votes over the limit are taken out of the ongoing referendum.
+		StorageVersion::new(1).put::<Pallet<Test>>();
+		let proposal = Call::DAO(dao::Call::dao_remark { dao_id: 0u64, remark: vec![1; 10] });
+		migrations::v1::ReferendumInfoOf::<Test>::insert(
+			0u64,
+			0u32,
+			migrations::v1::OldReferendumInfo::Ongoing(migrations::v1::OldReferendumStatus {
+				end: 40u64,
+				proposal,
+				delay: 50u64,
+				tally: migrations::v1::OldTally { ayes: 10100u64, nays: 0u64 },
+			}),
+		);
+		let vote = VoteInfo {
+			dao_id: 0u64,
+			concrete_id: Nft(0u64),
+			pledge: Vote(100u64),
+			opinion: Opinion::AYES,
+			vote_weight: 100u64,
+			unlock_block: 60u64,
+			referendum_index: 0u32,
+		};
+		migrations::v1::VotesOf::<Test>::insert(ALICE, vec![vote; 101]);
+		migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(VotesOf::<Test>::get(ALICE).len(), 100);
+		match migrations::v1::ReferendumInfoOf::<Test>::get(0u64, 0u32) {
+			Some(migrations::v1::OldReferendumInfo::Ongoing(x)) =>
+				assert_eq!((x.tally.ayes, x.tally.nays), (10000u64, 0u64)),
+			_ => panic!("The referendum is not ongoing."),
+		}
+	});
+}
+
+#[test]
+pub fn locked_pledge_should_work() {
+	new_test_ext().execute_with(|| {
+		let lock_of =
+			|who: u64| pallet_balances::Locks::<Test>::get(who).iter().map(|l| l.amount).max();
+		VoteLockingPeriod::<Test>::insert(0u64, 10u64);
+		assert_eq!(Conviction::None.votes(50u64), 5u64);
+		assert_noop!(
+			LockedPledge::<Test>::new(200u64).try_vote(&10u64, &0u64, &Conviction::Locked1x),
+			Error::<Test>::PledgeNotEnough
+		);
+		assert_eq!(
+			LockedPledge::<Test>::new(50u64).try_vote(&10u64, &0u64, &Conviction::Locked2x),
+			Ok((100u64, 20u64))
+		);
+		assert_eq!(
+			LockedPledge::<Test>::new(80u64).try_vote(&10u64, &0u64, &Conviction::Locked1x),
+			Ok((80u64, 10u64))
+		);
+		assert_eq!(lock_of(10u64), Some(80u64));
+
+		// The smaller vote is still locked after the larger one ends.
+		frame_system::Pallet::<Test>::set_block_number(10);
+		assert_ok!(LockedPledge::<Test>::new(80u64).vote_end_do(&10u64, &0u64));
+		assert_eq!(lock_of(10u64), Some(50u64));
+		assert_eq!(VoteLocks::<Test>::get(10u64), vec![(0u64, 50u64, 20u64)]);
+
+		frame_system::Pallet::<Test>::set_block_number(20);
+		assert_ok!(LockedPledge::<Test>::new(50u64).vote_end_do(&10u64, &0u64));
+		assert_eq!(lock_of(10u64), None);
+		assert!(!VoteLocks::<Test>::contains_key(10u64));
+	});
+}
+
+#[test]
+pub fn delegate_should_not_chain() {
+	new_test_ext().execute_with(|| {
+		create_dao();
+		assert_ok!(crate::Pallet::<Test>::delegate(
+			Origin::signed(10u64),
+			0u64,
+			20u64,
+			Vote(100u64),
+			()
+		));
+		assert_noop!(
+			crate::Pallet::<Test>::delegate(Origin::signed(20u64), 0u64, 30u64, Vote(100u64), ()),
+			Error::<Test>::HasDelegators
+		);
+		assert_noop!(
+			crate::Pallet::<Test>::delegate(Origin::signed(30u64), 0u64, 10u64, Vote(100u64), ()),
+			Error::<Test>::TargetDelegating
+		);
+
+		// The delegate can delegate once the weight is taken back.
+		assert_ok!(crate::Pallet::<Test>::undelegate(Origin::signed(10u64), 0u64));
+		assert_ok!(crate::Pallet::<Test>::delegate(
+			Origin::signed(20u64),
+			0u64,
+			30u64,
+			Vote(100u64),
+			()
+		));
+	});
+}
+
+#[test]
+pub fn delegate_should_work() {
+	new_test_ext().execute_with(|| {
+		open_table();
+		let tally = || crate::Pallet::<Test>::referendum_tally(0u64, 0u32).unwrap();
+		assert_ok!(crate::Pallet::<Test>::vote_for_referendum(
+			Origin::signed(ALICE),
+			0u64,
+			0u32,
+			Vote(100u64),
+			(),
+			Opinion::AYES,
+		));
+		assert_ok!(crate::Pallet::<Test>::delegate(
+			Origin::signed(2u64),
+			0u64,
+			ALICE,
+			Vote(100u64),
+			()
+		));
+		assert_eq!(tally().ayes, 200u64);
+		assert_eq!(DelegatedWeightOf::<Test>::get(0u64, ALICE), 100u64);
+		assert_noop!(
+			crate::Pallet::<Test>::vote_for_referendum(
+				Origin::signed(2u64),
+				0u64,
+				0u32,
+				Vote(100u64),
+				(),
+				Opinion::NAYS,
+			),
+			Error::<Test>::AlreadyDelegating
+		);
+		assert_noop!(
+			crate::Pallet::<Test>::delegate(Origin::signed(3u64), 0u64, 3u64, Vote(100u64), ()),
+			Error::<Test>::DelegateToSelf
+		);
+		assert_noop!(
+			crate::Pallet::<Test>::delegate(Origin::signed(10u64), 0u64, 2u64, Vote(100u64), ()),
+			Error::<Test>::TargetDelegating
+		);
+		assert_noop!(
+			crate::Pallet::<Test>::delegate(Origin::signed(ALICE), 0u64, 3u64, Vote(100u64), ()),
+			Error::<Test>::AlreadyVoting
+		);
+		assert_ok!(crate::Pallet::<Test>::delegate(
+			Origin::signed(3u64),
+			0u64,
+			ALICE,
+			Vote(100u64),
+			()
+		));
+		assert_eq!(tally().ayes, 300u64);
+
+		assert_ok!(crate::Pallet::<Test>::undelegate(Origin::signed(2u64), 0u64));
+		assert_eq!(tally().ayes, 200u64);
+		assert_eq!(DelegatedWeightOf::<Test>::get(0u64, ALICE), 100u64);
+		assert_noop!(
+			crate::Pallet::<Test>::undelegate(Origin::signed(2u64), 0u64),
+			Error::<Test>::NotDelegating
+		);
+
+		// The delegated weight is added to the new votes of the delegate.
+		assert_ok!(crate::Pallet::<Test>::cancel_vote(Origin::signed(ALICE), 0u64, 0u32));
+		assert_eq!(tally().ayes, 0u64);
+		assert_ok!(crate::Pallet::<Test>::vote_for_referendum(
+			Origin::signed(ALICE),
+			0u64,
+			0u32,
+			Vote(100u64),
+			(),
+			Opinion::NAYS,
+		));
+		assert_eq!(tally().nays, 200u64);
+
+		assert_ok!(crate::Pallet::<Test>::unlock(Origin::signed(2u64)));
+		assert_eq!(UndelegatedOf::<Test>::get(2u64).len(), 1);
+		frame_system::Pallet::<Test>::set_block_number(10100);
+		assert_ok!(crate::Pallet::<Test>::unlock(Origin::signed(2u64)));
+		assert!(UndelegatedOf::<Test>::get(2u64).is_empty());
+	});
+}
+
+#[test]
+pub fn migrate_to_v3_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(2).put::<Pallet<Test>>();
+		let proposal = Call::DAO(dao::Call::dao_remark { dao_id: 0u64, remark: vec![1; 10] });
+		migrations::v1::ReferendumInfoOf::<Test>::insert(
+			0u64,
+			0u32,
+			migrations::v1::OldReferendumInfo::Ongoing(migrations::v1::OldReferendumStatus {
+				end: 40u64,
+				proposal,
+				delay: 50u64,
+				tally: migrations::v1::OldTally { ayes: 10u64, nays: 20u64 },
+			}),
+		);
+
+		migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<Pallet<Test>>(), 3);
+		match migrations::v3::ReferendumInfoOf::<Test>::get(0u64, 0u32) {
+			Some(ReferendumInfo::Ongoing(x)) => assert_eq!(
+				(x.tally.ayes, x.tally.nays, x.tally.abstentions),
+				(10u64, 20u64, 0u64)
+			),
+			_ => panic!("The referendum is not migrated."),
+		}
+	});
+}
+
+#[test]
+pub fn migrate_to_v4_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(3).put::<Pallet<Test>>();
+		let proposal = Call::DAO(dao::Call::dao_remark { dao_id: 0u64, remark: vec![1; 10] });
+		let long_proposal = Call::DAO(dao::Call::dao_remark { dao_id: 0u64, remark: vec![1; 200] });
+		migrations::v3::PublicProps::<Test>::insert(
+			0u64,
+			vec![(0u32, BlakeTwo256::hash_of(&proposal), proposal.clone(), ALICE)],
+		);
+		migrations::v3::ReferendumInfoOf::<Test>::insert(
+			0u64,
+			0u32,
+			ReferendumInfo::Ongoing(ReferendumStatus {
+				end: 40u64,
+				proposal: long_proposal.clone(),
+				delay: 50u64,
+				tally: Tally { ayes: 10u64, nays: 20u64, abstentions: 0u64 },
+			}),
+		);
+
+		migrations::v4::MigrateToV4::<Test>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<Pallet<Test>>(), 4);
+		assert_eq!(
+			crate::Pallet::<Test>::open_proposals(0u64),
+			vec![(0u32, BlakeTwo256::hash_of(&proposal), ALICE)]
+		);
+		match ReferendumInfoOf::<Test>::get(0u64, 0u32) {
+			Some(ReferendumInfo::Ongoing(x)) => {
+				// The long call is stored in the preimages.
+				assert!(x.proposal.lookup_hash().is_some());
+				assert!(Preimage::is_requested(&x.proposal.hash()));
+				assert_eq!(Preimage::peek(&x.proposal).unwrap().0, long_proposal);
+			},
+			_ => panic!("The referendum is not migrated."),
+		}
+	});
+}
+
+#[test]
+pub fn preimage_should_be_requested_until_enacted() {
+	new_test_ext().execute_with(|| {
+		create_dao();
+		let proposal = Call::Square(crate::Call::set_min_vote_weight_for_every_call {
+			dao_id: 0u64,
+			call_id: set_min_vote_weight_id(),
+			min_vote_weight: 100u64,
+		});
+		let encoded = proposal.encode();
+		let hash = BlakeTwo256::hash(&encoded[..]);
+		// Propose by the hash before the preimage is noted.
+		assert_ok!(crate::Pallet::<Test>::propose(
+			Origin::signed(ALICE),
+			0u64,
+			Bounded::Lookup { hash, len: encoded.len() as u32 },
+			0u64
+		));
+		assert!(Preimage::is_requested(&hash));
+		assert_eq!(crate::Pallet::<Test>::open_proposals(0u64)[0].1, hash);
+		frame_system::Pallet::<Test>::set_block_number(10000);
+		assert_ok!(crate::Pallet::<Test>::open_table(Origin::signed(ALICE), 0u64));
+		let votes = [(ALICE, Opinion::AYES), (2u64, Opinion::AYES), (3u64, Opinion::NAYS)];
+		for (who, opinion) in votes {
+			assert_ok!(crate::Pallet::<Test>::vote_for_referendum(
+				Origin::signed(who),
+				0u64,
+				0u32,
+				Vote(100u64),
+				(),
+				opinion,
+			));
+		}
+
+		frame_system::Pallet::<Test>::set_block_number(20000);
+		assert_noop!(
+			crate::Pallet::<Test>::enact_proposal(Origin::signed(ALICE), 0u64, 0u32),
+			Error::<Test>::PreimageMissing
+		);
+		assert_ok!(Preimage::note_preimage(Origin::signed(2u64), encoded));
+		assert_ok!(crate::Pallet::<Test>::enact_proposal(Origin::signed(ALICE), 0u64, 0u32));
+		assert_eq!(MinVoteWeightOf::<Test>::get(0u64, set_min_vote_weight_id()), 100u64);
+		assert!(!Preimage::is_requested(&hash));
+	});
+}
+
+#[test]
+pub fn split_and_abstain_should_work() {
+	new_test_ext().execute_with(|| {
+		vote();
+		assert_noop!(
+			crate::Pallet::<Test>::vote_for_referendum(
+				Origin::signed(10u64),
+				0u64,
+				0u32,
+				Vote(100u64),
+				(),
+				Opinion::Split { aye: 0u64, nay: 0u64 },
+			),
+			Error::<Test>::InvalidSplit
+		);
+		assert_ok!(crate::Pallet::<Test>::vote_for_referendum(
+			Origin::signed(10u64),
+			0u64,
+			0u32,
+			Vote(100u64),
+			(),
+			Opinion::Split { aye: 1u64, nay: 3u64 },
+		));
+		assert_ok!(crate::Pallet::<Test>::vote_for_referendum(
+			Origin::signed(20u64),
+			0u64,
+			0u32,
+			Vote(100u64),
+			(),
+			Opinion::Abstain,
+		));
+		let tally = crate::Pallet::<Test>::referendum_tally(0u64, 0u32).unwrap();
+		assert_eq!((tally.ayes, tally.nays, tally.abstentions), (225u64, 175u64, 100u64));
+
+		// The abstentions count toward the turnout.
+		MinVoteWeightOf::<Test>::insert(0u64, set_min_vote_weight_id(), 450u64);
+		frame_system::Pallet::<Test>::set_block_number(20000);
+		assert_ok!(crate::Pallet::<Test>::enact_proposal(Origin::signed(ALICE), 0u64, 0u32));
+		assert!(matches!(
+			ReferendumInfoOf::<Test>::get(0u64, 0u32),
+			Some(ReferendumInfo::Finished { approved: true, .. })
+		));
+	});
+}
+
+#[test]
+pub fn vote_threshold_should_work() {
+	let tally = |ayes: u128, nays: u128| Tally { ayes, nays, abstentions: 0u128 };
+	assert!(!VoteThreshold::SuperMajorityApprove.approved(&tally(60, 50), 210));
+	assert!(VoteThreshold::SuperMajorityApprove.approved(&tally(100, 50), 210));
+	assert!(!VoteThreshold::SuperMajorityApprove.approved(&tally(100, 50), 0));
+	assert!(!VoteThreshold::SimpleMajority.approved(&tally(0, 0), 210));
+	// Overflow is detected.
+	assert!(
+		!VoteThreshold::SuperMajorityApprove.approved(&tally(1 << 127, (1 << 127) + 1), u128::MAX)
+	);
+	assert!(
+		VoteThreshold::SuperMajorityAgainst.approved(&tally((1 << 127) + 1, 1 << 127), u128::MAX)
+	);
+}
+
+#[test]
+pub fn enact_proposal_with_threshold_should_work() {
+	new_test_ext().execute_with(|| {
+		vote();
+		assert_ok!(crate::Pallet::<Test>::vote_for_referendum(
+			Origin::signed(10u64),
+			0u64,
+			0u32,
+			Vote(100u64),
+			(),
+			Opinion::NAYS,
+		));
+		let dao_account = dao::Daos::<Test>::get(0u64).unwrap().dao_account_id;
+		assert_ok!(crate::Pallet::<Test>::set_vote_threshold_for_every_call(
+			Origin::signed(dao_account),
+			0u64,
+			set_min_vote_weight_id(),
+			Some(VoteThreshold::SimpleMajority)
+		));
+		assert_noop!(
+			crate::Pallet::<Test>::set_referendum_threshold(
+				Origin::signed(dao_account),
+				0u64,
+				1u32,
+				Some(VoteThreshold::SimpleMajority)
+			),
+			Error::<Test>::ReferendumNotExists
+		);
+
+		// A tie is rejected by a simple majority.
+		let tally = crate::Pallet::<Test>::referendum_tally(0u64, 0u32).unwrap();
+		assert!(!VoteThreshold::SimpleMajority.approved(&tally, Vote::electorate(&0u64)));
+
+		// The threshold of the referendum takes precedence.
+		frame_system::Pallet::<Test>::set_block_number(20000);
+		assert_ok!(crate::Pallet::<Test>::set_referendum_threshold(
+			Origin::signed(dao_account),
+			0u64,
+			0u32,
+			Some(VoteThreshold::SuperMajorityAgainst)
+		));
+		assert_ok!(crate::Pallet::<Test>::enact_proposal(Origin::signed(ALICE), 0u64, 0u32));
+		assert!(matches!(
+			ReferendumInfoOf::<Test>::get(0u64, 0u32),
+			Some(ReferendumInfo::Finished { approved: true, .. })
+		));
+		assert!(ReferendumThresholdOf::<Test>::get(0u64, 0u32).is_none());
+	});
+}
+
+#[test]
+pub fn tie_without_threshold_should_pass() {
+	new_test_ext().execute_with(|| {
+		vote();
+		assert_ok!(crate::Pallet::<Test>::vote_for_referendum(
+			Origin::signed(10u64),
+			0u64,
+			0u32,
+			Vote(100u64),
+			(),
+			Opinion::NAYS,
+		));
+		frame_system::Pallet::<Test>::set_block_number(20000);
+		assert_ok!(crate::Pallet::<Test>::enact_proposal(Origin::signed(ALICE), 0u64, 0u32));
+		assert!(matches!(
+			ReferendumInfoOf::<Test>::get(0u64, 0u32),
+			Some(ReferendumInfo::Finished { approved: true, .. })
+		));
+	});
+}
+
+#[test]
+pub fn referendum_should_be_launched_and_enacted_automatically() {
+	new_test_ext().execute_with(|| {
+		second();
+		assert_eq!(NextLaunch::<Test>::get(0u64), Some(1u64));
+		assert_eq!(Agenda::<Test>::get(1u64), vec![Task::Launch(0u64)]);
+		run_to_block(1);
+		assert!(matches!(
+			ReferendumInfoOf::<Test>::get(0u64, 0u32),
+			Some(ReferendumInfo::Ongoing(_))
+		));
+		assert!(Agenda::<Test>::get(1u64).is_empty());
+		// No proposal is waiting.
+		assert_eq!(NextLaunch::<Test>::get(0u64), None);
+
+		// The next referendum is opened after the launch period.
+		let proposal = Call::Square(crate::Call::set_min_vote_weight_for_every_call {
+			dao_id: 0u64,
+			call_id: set_min_vote_weight_id(),
+			min_vote_weight: 200u64,
+		});
+		assert_ok!(crate::Pallet::<Test>::propose(
+			Origin::signed(ALICE),
+			0u64,
+			bounded(proposal),
+			0u64
+		));
+		let launch_period = LaunchPeriod::<Test>::get(0u64);
+		assert_eq!(NextLaunch::<Test>::get(0u64), Some(launch_period + 1));
+		assert_ok!(crate::Pallet::<Test>::vote_for_referendum(
+			Origin::signed(ALICE),
+			0u64,
+			0u32,
+			Vote(100u64),
+			(),
+			Opinion::AYES,
+		));
+
+		let enact_block = 1 + VotingPeriod::<Test>::get(0u64) + EnactmentPeriod::<Test>::get(0u64);
+		run_to_block(enact_block - 1);
+		assert!(matches!(
+			ReferendumInfoOf::<Test>::get(0u64, 0u32),
+			Some(ReferendumInfo::Ongoing(_))
+		));
+		assert!(matches!(
+			ReferendumInfoOf::<Test>::get(0u64, 1u32),
+			Some(ReferendumInfo::Ongoing(_))
+		));
+		run_to_block(enact_block);
+		assert!(matches!(
+			ReferendumInfoOf::<Test>::get(0u64, 0u32),
+			Some(ReferendumInfo::Finished { approved: true, .. })
+		));
+		assert_eq!(MinVoteWeightOf::<Test>::get(0u64, set_min_vote_weight_id()), 100u64);
+	});
+}
+
+#[test]
+pub fn task_should_be_moved_when_the_agenda_is_full() {
+	new_test_ext().execute_with(|| {
+		let full = (0..10u32).map(|i| Task::Enact(1u64, i)).collect::<Vec<_>>();
+		Agenda::<Test>::insert(1u64, BoundedVec::truncate_from(full));
+		second();
+		assert_eq!(NextLaunch::<Test>::get(0u64), Some(2u64));
+		assert_eq!(Agenda::<Test>::get(2u64), vec![Task::Launch(0u64)]);
+		run_to_block(2);
+		assert!(matches!(
+			ReferendumInfoOf::<Test>::get(0u64, 0u32),
+			Some(ReferendumInfo::Ongoing(_))
+		));
+		assert_eq!(NextLaunch::<Test>::get(0u64), None);
+	});
+}
+
+fn propose_with_deposit(deposit: u64) -> Call {
+	create_dao();
+	let proposal = Call::DAO(dao::Call::dao_remark { dao_id: 0u64, remark: vec![1; 10] });
+	assert_ok!(crate::Pallet::<Test>::propose(
+		Origin::signed(ALICE),
+		0u64,
+		bounded(proposal.clone()),
+		deposit
+	));
+	assert_ok!(crate::Pallet::<Test>::second(Origin::signed(2u64), 0u64, 0u32));
+	assert_ok!(crate::Pallet::<Test>::second(Origin::signed(3u64), 0u64, 0u32));
+	assert_eq!(Balances::reserved_balance(ALICE), deposit);
+	assert_eq!(Balances::reserved_balance(2u64), deposit);
+	proposal
+}
+
+#[test]
+pub fn deposits_should_be_returned_when_launched() {
+	new_test_ext().execute_with(|| {
+		propose_with_deposit(5u64);
+		frame_system::Pallet::<Test>::set_block_number(10000);
+		assert_ok!(crate::Pallet::<Test>::open_table(Origin::signed(ALICE), 0u64));
+		assert!(DepositOf::<Test>::get(0u64, 0u32).is_none());
+		for who in [ALICE, 2u64, 3u64] {
+			assert_eq!(Balances::reserved_balance(who), 0u64);
+			assert!(ReserveOf::<Test>::get(who).is_empty());
+		}
+	});
+}
+
+#[test]
+pub fn withdraw_proposal_should_work() {
+	new_test_ext().execute_with(|| {
+		propose_with_deposit(5u64);
+		assert_noop!(
+			crate::Pallet::<Test>::withdraw_proposal(Origin::signed(2u64), 0u64, 0u32),
+			Error::<Test>::NotProposer
+		);
+		assert_noop!(
+			crate::Pallet::<Test>::withdraw_proposal(Origin::signed(ALICE), 0u64, 1u32),
+			Error::<Test>::ProposalMissing
+		);
+		assert_ok!(crate::Pallet::<Test>::withdraw_proposal(Origin::signed(ALICE), 0u64, 0u32));
+		assert!(PublicProps::<Test>::get(0u64).is_empty());
+		assert!(DepositOf::<Test>::get(0u64, 0u32).is_none());
+		for who in [ALICE, 2u64, 3u64] {
+			assert_eq!(Balances::reserved_balance(who), 0u64);
+			assert_eq!(Balances::free_balance(who), 10u64);
+		}
+	});
+}
+
+#[test]
+pub fn blacklist_should_slash_deposits() {
+	new_test_ext().execute_with(|| {
+		let proposal = propose_with_deposit(5u64);
+		let proposal_hash = BlakeTwo256::hash_of(&proposal);
+		assert_noop!(
+			crate::Pallet::<Test>::blacklist(Origin::signed(ALICE), 0u64, proposal_hash),
+			dao::Error::<Test>::BadOrigin
+		);
+		let dao_account = dao::Daos::<Test>::get(0u64).unwrap().dao_account_id;
+		assert_ok!(crate::Pallet::<Test>::blacklist(
+			Origin::signed(dao_account),
+			0u64,
+			proposal_hash
+		));
+		assert!(PublicProps::<Test>::get(0u64).is_empty());
+		assert!(DepositOf::<Test>::get(0u64, 0u32).is_none());
+		for who in [ALICE, 2u64, 3u64] {
+			assert_eq!(Balances::reserved_balance(who), 0u64);
+			assert_eq!(Balances::free_balance(who), 5u64);
+		}
+		assert_noop!(
+			crate::Pallet::<Test>::propose(Origin::signed(ALICE), 0u64, bounded(proposal), 0u64),
+			Error::<Test>::ProposalBlacklisted
+		);
+	});
+}
+
+#[test]
+pub fn blacklist_should_reject_ongoing_referendums() {
+	new_test_ext().execute_with(|| {
+		vote();
+		let proposal_hash = match ReferendumInfoOf::<Test>::get(0u64, 0u32) {
+			Some(ReferendumInfo::Ongoing(x)) => x.proposal.hash(),
+			_ => panic!("The referendum is not ongoing."),
+		};
+		let dao_account = dao::Daos::<Test>::get(0u64).unwrap().dao_account_id;
+		assert_ok!(crate::Pallet::<Test>::blacklist(
+			Origin::signed(dao_account),
+			0u64,
+			proposal_hash
+		));
+		assert_ok!(crate::Pallet::<Test>::enact_proposal(Origin::signed(ALICE), 0u64, 0u32));
+		System::assert_last_event(crate::mock::Event::Square(crate::Event::EnactProposal {
+			dao_id: 0u64,
+			index: 0u32,
+			result: Err(Error::<Test>::ProposalBlacklisted.into()),
+		}));
+		assert!(matches!(
+			ReferendumInfoOf::<Test>::get(0u64, 0u32),
+			Some(ReferendumInfo::Finished { approved: false, .. })
+		));
+	});
+}
+
+#[test]
+pub fn migrate_to_v5_should_work() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(4).put::<Pallet<Test>>();
+		DepositOf::<Test>::insert(0u64, 0u32, (BoundedVec::truncate_from(vec![ALICE, 2u64]), 5u64));
+		// The reserve of the second of 3u64 is already released by `unlock`.
+		DepositOf::<Test>::insert(
+			0u64,
+			1u32,
+			(BoundedVec::truncate_from(vec![ALICE, 3u64, 2u64]), 3u64),
+		);
+		ReserveOf::<Test>::insert(
+			2u64,
+			BoundedVec::truncate_from(vec![(3u64, 30u64), (5u64, 40u64)]),
+		);
+		ReserveOf::<Test>::insert(3u64, BoundedVec::truncate_from(vec![(5u64, 40u64)]));
+
+		migrations::v5::MigrateToV5::<Test>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<Pallet<Test>>(), 5);
+		assert!(!ReserveOf::<Test>::contains_key(2u64));
+		assert_eq!(ReserveOf::<Test>::get(3u64), vec![(5u64, 40u64)]);
+		assert!(!ReserveOf::<Test>::contains_key(ALICE));
+		assert_eq!(DepositOf::<Test>::get(0u64, 0u32).unwrap().0, vec![ALICE, 2u64]);
+		assert_eq!(DepositOf::<Test>::get(0u64, 1u32).unwrap().0, vec![ALICE, 2u64]);
+	});
+}
+
+#[test]
+pub fn locked_vote_should_last_until_the_referendum_ends() {
+	use crate::mock::locked::{self, LockedTest};
+	locked::new_test_ext().execute_with(|| {
+		let lock_of = |who: u64| {
+			pallet_balances::Locks::<LockedTest>::get(who).iter().map(|l| l.amount).max()
+		};
+		let unlock = |who: u64| crate::Pallet::<LockedTest>::unlock(locked::Origin::signed(who));
+		dao::Pallet::<LockedTest>::create_dao(
+			locked::Origin::signed(ALICE),
+			Nft(0u64),
+			vec![1; 4],
+			None,
+		)
+		.unwrap();
+		let proposal =
+			locked::Call::DAO(dao::Call::dao_remark { dao_id: 0u64, remark: vec![1; 10] });
+		assert_ok!(crate::Pallet::<LockedTest>::propose(
+			locked::Origin::signed(ALICE),
+			0u64,
+			locked::Preimage::bound(proposal).unwrap(),
+			0u64
+		));
+		frame_system::Pallet::<LockedTest>::set_block_number(10000);
+		assert_ok!(crate::Pallet::<LockedTest>::open_table(locked::Origin::signed(ALICE), 0u64));
+		let end = 10000 + VotingPeriod::<LockedTest>::get(0u64);
+		let period = VoteLockingPeriod::<LockedTest>::get(0u64);
+
+		assert_ok!(crate::Pallet::<LockedTest>::vote_for_referendum(
+			locked::Origin::signed(ALICE),
+			0u64,
+			0u32,
+			LockedPledge::new(500u64),
+			Conviction::None,
+			Opinion::AYES,
+		));
+		assert_ok!(crate::Pallet::<LockedTest>::vote_for_referendum(
+			locked::Origin::signed(2u64),
+			0u64,
+			0u32,
+			LockedPledge::new(300u64),
+			Conviction::Locked1x,
+			Opinion::NAYS,
+		));
+		assert_eq!(VotesOf::<LockedTest>::get(ALICE)[0].unlock_block, end);
+		assert_eq!(VotesOf::<LockedTest>::get(2u64)[0].unlock_block, end + period);
+
+		// A vote without conviction is not released in the block it is cast.
+		assert_ok!(unlock(ALICE));
+		assert_eq!(lock_of(ALICE), Some(500u64));
+		assert_eq!(VotesOf::<LockedTest>::get(ALICE).len(), 1);
+
+		frame_system::Pallet::<LockedTest>::set_block_number(end);
+		assert_ok!(unlock(ALICE));
+		assert_eq!(lock_of(ALICE), None);
+		assert_ok!(unlock(2u64));
+		assert_eq!(lock_of(2u64), Some(300u64));
+
+		frame_system::Pallet::<LockedTest>::set_block_number(end + period);
+		assert_ok!(unlock(2u64));
+		assert_eq!(lock_of(2u64), None);
+	});
+}
+
+#[test]
+pub fn enact_without_enough_vote_weight_should_reject() {
+	new_test_ext().execute_with(|| {
+		vote();
+		MinVoteWeightOf::<Test>::insert(0u64, set_min_vote_weight_id(), 10000000000);
+		frame_system::Pallet::<Test>::set_block_number(20000);
+		assert_ok!(crate::Pallet::<Test>::enact_proposal(Origin::signed(ALICE), 0u64, 0u32));
+		System::assert_last_event(crate::mock::Event::Square(crate::Event::EnactProposal {
+			dao_id: 0u64,
+			index: 0u32,
+			result: Err(Error::<Test>::VoteWeightTooLow.into()),
+		}));
+		assert!(matches!(
+			ReferendumInfoOf::<Test>::get(0u64, 0u32),
+			Some(ReferendumInfo::Finished { approved: false, .. })
+		));
+		assert_noop!(
+			crate::Pallet::<Test>::enact_proposal(Origin::signed(ALICE), 0u64, 0u32),
+			Error::<Test>::ReferendumFinished
+		);
+	});
+}
+
+#[test]
+pub fn migrate_to_v6_should_work() {
+	new_test_ext().execute_with(|| {
+		vote();
+		assert_ok!(crate::Pallet::<Test>::propose(
+			Origin::signed(ALICE),
+			0u64,
+			bounded(Call::DAO(dao::Call::dao_remark { dao_id: 0u64, remark: vec![1; 10] })),
+			0u64
+		));
+		// Nothing was scheduled before the upgrade.
+		let _ = Agenda::<Test>::clear(u32::MAX, None);
+		let _ = NextLaunch::<Test>::clear(u32::MAX, None);
+		StorageVersion::new(5).put::<Pallet<Test>>();
+		let enact_block = match ReferendumInfoOf::<Test>::get(0u64, 0u32) {
+			Some(ReferendumInfo::Ongoing(x)) => (x.end + x.delay).max(20001u64),
+			_ => panic!("The referendum is not ongoing."),
+		};
+
+		migrations::v6::MigrateToV6::<Test>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<Pallet<Test>>(), 6);
+		assert!(Agenda::<Test>::get(enact_block).contains(&Task::Enact(0u64, 0u32)));
+		let launch_block = NextLaunch::<Test>::get(0u64).unwrap();
+		assert!(Agenda::<Test>::get(launch_block).contains(&Task::Launch(0u64)));
+	});
+}
@@ -9,8 +9,11 @@ pub trait Pledge<VoteWeight, AccountId, DaoId, Convivtion, BlockNumber, Dispatch
 		conviction: &Convivtion,
 	) -> result::Result<(VoteWeight, BlockNumber), DispatchError>;
 	fn vote_end_do(&self, who: &AccountId, dao_id: &DaoId) -> result::Result<(), DispatchError>;
+	/// The total vote weight that can vote in the referendums of the DAO.
+	fn electorate(dao_id: &DaoId) -> VoteWeight;
 }
 
+/// Convert the conviction of a vote into the number of periods that it is locked for.
 pub trait ConvertInto<A> {
 	fn convert_into(&self) -> A;
 }
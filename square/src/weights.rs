@@ -43,6 +43,13 @@ pub trait WeightInfo {
     fn set_voting_period() -> Weight;
     fn set_rerserve_period() -> Weight;
     fn set_enactment_period() -> Weight;
+    fn set_vote_locking_period() -> Weight;
+    fn delegate() -> Weight;
+    fn undelegate() -> Weight;
+    fn set_vote_threshold_for_every_call() -> Weight;
+    fn set_referendum_threshold() -> Weight;
+    fn withdraw_proposal() -> Weight;
+    fn blacklist() -> Weight;
 }
 
 /// Weights for daos_square using the Substrate node and recommended hardware.
@@ -55,13 +62,12 @@ pub struct DaosWeight<T>(PhantomData<T>);
             // Storage: DaoSquare PublicProps (r:1 w:1)
             // Storage: DaoSquare MaxPublicProps (r:1 w:0)
             // Storage: DaoSquare DepositOf (r:0 w:1)
+            // Storage: Preimage StatusFor (r:1 w:1)
         fn propose() -> Weight {
                 Weight::from_all(2000_0000)
         }
             // Storage: DaoSquare DepositOf (r:1 w:1)
             // Storage: System Account (r:1 w:1)
-            // Storage: DaoSquare ReservePeriod (r:1 w:0)
-            // Storage: DaoSquare ReserveOf (r:1 w:1)
         fn second() -> Weight {
             Weight::from_all(2000_0000)
         }
@@ -91,6 +97,8 @@ pub struct DaosWeight<T>(PhantomData<T>);
             // Storage: DaoSquare ReferendumInfoOf (r:1 w:1)
             // Storage: DaoSquare MinVoteWeightOf (r:1 w:0)
             // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: Preimage StatusFor (r:1 w:1)
+            // Storage: Preimage PreimageFor (r:1 w:0)
         fn enact_proposal() -> Weight {
             Weight::from_all(2000_0000)
         }
@@ -134,6 +142,54 @@ pub struct DaosWeight<T>(PhantomData<T>);
         fn set_enactment_period() -> Weight {
             Weight::from_all(2000_0000)
         }
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: DaoSquare VoteLockingPeriod (r:0 w:1)
+        fn set_vote_locking_period() -> Weight {
+            Weight::from_all(2000_0000)
+        }
+            // Storage: DaoSquare Delegations (r:1 w:1)
+            // Storage: DaoSquare VotesOf (r:2 w:1)
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: DaoSquare DelegatedWeightOf (r:1 w:1)
+            // Storage: DaoSquare ReferendumInfoOf (r:1 w:1)
+        fn delegate() -> Weight {
+            Weight::from_all(2000_0000)
+        }
+            // Storage: DaoSquare Delegations (r:1 w:1)
+            // Storage: DaoSquare UndelegatedOf (r:1 w:1)
+            // Storage: DaoSquare DelegatedWeightOf (r:1 w:1)
+            // Storage: DaoSquare VotesOf (r:1 w:1)
+            // Storage: DaoSquare ReferendumInfoOf (r:1 w:1)
+        fn undelegate() -> Weight {
+            Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: DaoSquare VoteThresholdOf (r:0 w:1)
+        fn set_vote_threshold_for_every_call() -> Weight {
+            Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: DaoSquare ReferendumInfoOf (r:1 w:0)
+            // Storage: DaoSquare ReferendumThresholdOf (r:0 w:1)
+        fn set_referendum_threshold() -> Weight {
+            Weight::from_all(2000_0000)
+        }
+            // Storage: DaoSquare PublicProps (r:1 w:1)
+            // Storage: Preimage StatusFor (r:1 w:1)
+            // Storage: DaoSquare DepositOf (r:1 w:1)
+            // Storage: System Account (r:3 w:3)
+        fn withdraw_proposal() -> Weight {
+            Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: DaoSquare Blacklist (r:0 w:1)
+            // Storage: DaoSquare PublicProps (r:1 w:1)
+            // Storage: Preimage StatusFor (r:1 w:1)
+            // Storage: DaoSquare DepositOf (r:1 w:1)
+            // Storage: System Account (r:3 w:3)
+        fn blacklist() -> Weight {
+            Weight::from_all(2000_0000)
+        }
     }
 
     // For backwards compatibility and tests
@@ -145,13 +201,12 @@ pub struct DaosWeight<T>(PhantomData<T>);
             // Storage: DaoSquare PublicProps (r:1 w:1)
             // Storage: DaoSquare MaxPublicProps (r:1 w:0)
             // Storage: DaoSquare DepositOf (r:0 w:1)
+            // Storage: Preimage StatusFor (r:1 w:1)
         fn propose() -> Weight {
             Weight::from_all(2000_0000)
         }
             // Storage: DaoSquare DepositOf (r:1 w:1)
             // Storage: System Account (r:1 w:1)
-            // Storage: DaoSquare ReservePeriod (r:1 w:0)
-            // Storage: DaoSquare ReserveOf (r:1 w:1)
         fn second() -> Weight {
             Weight::from_all(2000_0000)
         }
@@ -181,6 +236,8 @@ pub struct DaosWeight<T>(PhantomData<T>);
             // Storage: DaoSquare ReferendumInfoOf (r:1 w:1)
             // Storage: DaoSquare MinVoteWeightOf (r:1 w:0)
             // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: Preimage StatusFor (r:1 w:1)
+            // Storage: Preimage PreimageFor (r:1 w:0)
         fn enact_proposal() -> Weight {
             Weight::from_all(2000_0000)
         }
@@ -224,4 +281,52 @@ pub struct DaosWeight<T>(PhantomData<T>);
         fn set_enactment_period() -> Weight {
             Weight::from_all(2000_0000)
         }
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: DaoSquare VoteLockingPeriod (r:0 w:1)
+        fn set_vote_locking_period() -> Weight {
+            Weight::from_all(2000_0000)
+        }
+            // Storage: DaoSquare Delegations (r:1 w:1)
+            // Storage: DaoSquare VotesOf (r:2 w:1)
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: DaoSquare DelegatedWeightOf (r:1 w:1)
+            // Storage: DaoSquare ReferendumInfoOf (r:1 w:1)
+        fn delegate() -> Weight {
+            Weight::from_all(2000_0000)
+        }
+            // Storage: DaoSquare Delegations (r:1 w:1)
+            // Storage: DaoSquare UndelegatedOf (r:1 w:1)
+            // Storage: DaoSquare DelegatedWeightOf (r:1 w:1)
+            // Storage: DaoSquare VotesOf (r:1 w:1)
+            // Storage: DaoSquare ReferendumInfoOf (r:1 w:1)
+        fn undelegate() -> Weight {
+            Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: DaoSquare VoteThresholdOf (r:0 w:1)
+        fn set_vote_threshold_for_every_call() -> Weight {
+            Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: DaoSquare ReferendumInfoOf (r:1 w:0)
+            // Storage: DaoSquare ReferendumThresholdOf (r:0 w:1)
+        fn set_referendum_threshold() -> Weight {
+            Weight::from_all(2000_0000)
+        }
+            // Storage: DaoSquare PublicProps (r:1 w:1)
+            // Storage: Preimage StatusFor (r:1 w:1)
+            // Storage: DaoSquare DepositOf (r:1 w:1)
+            // Storage: System Account (r:3 w:3)
+        fn withdraw_proposal() -> Weight {
+            Weight::from_all(2000_0000)
+        }
+            // Storage: CreateDao Daos (r:1 w:0)
+            // Storage: DaoSquare Blacklist (r:0 w:1)
+            // Storage: DaoSquare PublicProps (r:1 w:1)
+            // Storage: Preimage StatusFor (r:1 w:1)
+            // Storage: DaoSquare DepositOf (r:1 w:1)
+            // Storage: System Account (r:3 w:3)
+        fn blacklist() -> Weight {
+            Weight::from_all(2000_0000)
+        }
    }
\ No newline at end of file
@@ -2,17 +2,20 @@
 
 use super::*;
 use crate::{Call as SudoCall, Config, Pallet as Sudo};
-use dao::Call as DaoCall;
+use dao::{Call as DaoCall, Currency};
 use frame_benchmarking::{
 	account, benchmarks, benchmarks_instance, benchmarks_instance_pallet,
 	impl_benchmark_test_suite, whitelisted_caller,
 };
 use frame_system::RawOrigin as SystemOrigin;
 use primitives::AccountIdConversion;
+use sp_runtime::traits::Bounded;
 use sp_std::vec;
 
 fn get_alice<T: Config>() -> T::AccountId {
-	account("alice", 1, 1)
+	let alice = account("alice", 1, 1);
+	<T as dao::Config>::Currency::make_free_balance_be(&alice, dao::BalanceOf::<T>::max_value());
+	alice
 }
 
 fn get_dao_account<T: Config>(second_id: T::ConcreteId) -> T::AccountId {
@@ -26,6 +29,7 @@ fn create_dao<T: Config>() -> (T::DaoId, T::ConcreteId) {
 		SystemOrigin::Signed(get_alice::<T>()).into(),
 		second_id,
 		vec![1; 4],
+		None,
 	)
 	.is_ok());
 	(dao_id, second_id)
@@ -23,11 +23,12 @@
 //! This module, very useful in the early stage of DAO creation, can be used to set basic parameters,
 //! but it also means centralization. So to achieve true decentralization should `close_sudo`.
 
-pub use dao::{self, BaseCallFilter};
+pub use dao::{self, AfterDissolve, ApplyTemplate, BaseCallFilter, DaoTemplateOf, Vec};
 pub use frame_support::{traits::UnfilteredDispatchable};
 pub use pallet::*;
 pub use scale_info::{prelude::boxed::Box, TypeInfo};
 pub use sp_std::{fmt::Debug, result};
+use sp_runtime::DispatchResult;
 use weights::WeightInfo;
 #[cfg(test)]
 mod mock;
@@ -53,9 +54,12 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
-	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Root account id.
@@ -63,6 +67,22 @@ pub mod pallet {
 	#[pallet::getter(fn sudo_account)]
 	pub type Account<T: Config> = StorageMap<_, Identity, T::DaoId, T::AccountId>;
 
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// The sudo account of each DAO.
+		pub accounts: Vec<(T::DaoId, T::AccountId)>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			for (dao_id, account) in self.accounts.iter() {
+				Account::<T>::insert(dao_id, account);
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -85,6 +105,7 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Execute external transactions as root
+		#[pallet::call_index(0)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::sudo())]
 		pub fn sudo(
 			origin: OriginFor<T>,
@@ -94,6 +115,8 @@ pub mod pallet {
 			let sudo = Self::check_origin(dao_id, origin)?;
 			let concrete_id = dao::Pallet::<T>::try_get_concrete_id(dao_id)?;
 			ensure!(concrete_id.contains(*call.clone()), dao::Error::<T>::InVailCall);
+			dao::Pallet::<T>::ensure_call_allowed(dao_id, &call)?;
+			dao::Pallet::<T>::ensure_can_dispatch(dao_id, &call)?;
 
 			let res = call.dispatch_bypass_filter(
 				frame_system::RawOrigin::Signed(dao::Pallet::<T>::try_get_dao_account_id(dao_id)?)
@@ -106,9 +129,8 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		/// call id: 401
-		///
 		/// Set root account or reopen sudo.
+		#[pallet::call_index(1)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_sudo_account())]
 		pub fn set_sudo_account(
 			origin: OriginFor<T>,
@@ -121,9 +143,8 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		/// call id: 402
-		///
 		/// delete root account.
+		#[pallet::call_index(2)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::close_sudo())]
 		pub fn close_sudo(origin: OriginFor<T>, dao_id: T::DaoId) -> DispatchResultWithPostInfo {
 			let _sudo = Self::check_origin(dao_id, origin)?;
@@ -152,3 +173,22 @@ pub mod pallet {
 		}
 	}
 }
+
+impl<T: Config> AfterDissolve<T::DaoId> for Pallet<T> {
+	fn do_something(dao_id: T::DaoId) {
+		Account::<T>::remove(dao_id);
+	}
+}
+
+impl<T: Config> ApplyTemplate<T::AccountId, T::DaoId, DaoTemplateOf<T>> for Pallet<T> {
+	fn apply_template(
+		creator: &T::AccountId,
+		dao_id: T::DaoId,
+		template: &DaoTemplateOf<T>,
+	) -> DispatchResult {
+		if template.sudo {
+			Account::<T>::insert(dao_id, creator.clone());
+		}
+		Ok(())
+	}
+}
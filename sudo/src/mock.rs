@@ -4,7 +4,7 @@
 use crate as sudo;
 use frame_support::traits::{ConstU16, ConstU32, ConstU64};
 use frame_system;
-use primitives::{ids::Nft, traits::BaseCallFilter};
+use primitives::{ids::Nft, mock::Collections, traits::BaseCallFilter, types::CallId};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -24,6 +24,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		DAO: dao::{ Pallet, Call, Event<T>, Storage },
 		Sudo: sudo::{ Pallet, Call, Event<T>, Storage },
 	}
@@ -56,14 +57,7 @@ impl frame_system::Config for Test {
 	type MaxConsumers = ConstU32<16>;
 }
 
-impl TryFrom<Call> for u64 {
-	type Error = ();
-	fn try_from(call: Call) -> Result<Self, Self::Error> {
-		match call {
-			_ => Ok(0u64),
-		}
-	}
-}
+primitives::impl_call_id!(Call);
 
 impl BaseCallFilter<Call> for Nft<u64> {
 	fn contains(&self, call: Call) -> bool {
@@ -71,13 +65,34 @@ impl BaseCallFilter<Call> for Nft<u64> {
 	}
 }
 
+impl pallet_balances::Config for Test {
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
 impl dao::Config for Test {
 	type Event = Event;
 	type Call = Call;
-	type CallId = u64;
+	type CallId = CallId;
 	type DaoId = u64;
 	type ConcreteId = Nft<u64>;
+	type Inspector = Collections;
 	type AfterCreate = ();
+	type AfterDissolve = Sudo;
+	type AfterCreatorChanged = ();
+	type ApplyTemplate = Sudo;
+	type MaxTemplateOrigins = ConstU32<10>;
+	type Currency = Balances;
+	type CreationDeposit = ConstU64<0>;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type StringLimit = ConstU32<50>;
 	type WeightInfo = ();
 }
 
@@ -87,6 +102,8 @@ impl sudo::Config for Test {
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	let t = GenesisConfig { system: Default::default() }.build_storage().unwrap();
+	let t = GenesisConfig { system: Default::default(), balances: Default::default() }
+		.build_storage()
+		.unwrap();
 	t.into()
 }
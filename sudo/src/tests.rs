@@ -2,14 +2,18 @@
 #![cfg(test)]
 
 use super::*;
-use crate::mock::{Call, *};
+use crate::mock::{Call, Event, *};
 use frame_support::{assert_noop, assert_ok, debug};
-use primitives::ids::Nft;
+use primitives::{ids::Nft, types::CallId};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	BuildStorage,
+};
 
 pub const ALICE: u64 = 1;
 
 pub fn create_dao() {
-	dao::Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 4]).unwrap();
+	dao::Pallet::<Test>::create_dao(Origin::signed(ALICE), Nft(0u64), vec![1; 4], None).unwrap();
 }
 
 pub fn set_sudo() {
@@ -52,3 +56,99 @@ pub fn close_sudo_should_work() {
 		assert_eq!(crate::Account::<Test>::get(0u64), None);
 	});
 }
+
+#[test]
+pub fn sudo_should_fail_when_dao_not_active() {
+	new_test_ext().execute_with(|| {
+		set_sudo();
+		let remark = Call::DAO(dao::Call::dao_remark { dao_id: 0u64, remark: vec![1; 10] });
+		assert_ok!(crate::Pallet::<Test>::sudo(
+			Origin::signed(ALICE),
+			0u64,
+			Box::new(Call::DAO(dao::Call::suspend_dao { dao_id: 0u64 }))
+		));
+		assert_noop!(
+			crate::Pallet::<Test>::sudo(Origin::signed(ALICE), 0u64, Box::new(remark.clone())),
+			dao::Error::<Test>::DaoNotActive
+		);
+		assert_ok!(crate::Pallet::<Test>::sudo(
+			Origin::signed(ALICE),
+			0u64,
+			Box::new(Call::DAO(dao::Call::resume_dao { dao_id: 0u64 }))
+		));
+		assert_ok!(crate::Pallet::<Test>::sudo(
+			Origin::signed(ALICE),
+			0u64,
+			Box::new(remark.clone())
+		));
+
+		assert_ok!(crate::Pallet::<Test>::sudo(
+			Origin::signed(ALICE),
+			0u64,
+			Box::new(Call::DAO(dao::Call::dissolve_dao { dao_id: 0u64 }))
+		));
+		assert_eq!(crate::Account::<Test>::get(0u64), None);
+		assert!(crate::Pallet::<Test>::sudo(Origin::signed(ALICE), 0u64, Box::new(remark)).is_err());
+	});
+}
+
+#[test]
+pub fn sudo_dispatch_as_sub_account_should_work() {
+	new_test_ext().execute_with(|| {
+		frame_system::Pallet::<Test>::set_block_number(1);
+		set_sudo();
+		assert_ok!(crate::Pallet::<Test>::sudo(
+			Origin::signed(ALICE),
+			0u64,
+			Box::new(Call::DAO(dao::Call::set_sub_account {
+				dao_id: 0u64,
+				index: 1u32,
+				allow: true
+			}))
+		));
+		let remark = Call::System(frame_system::Call::remark_with_event { remark: vec![1; 10] });
+		assert_ok!(crate::Pallet::<Test>::sudo(
+			Origin::signed(ALICE),
+			0u64,
+			Box::new(Call::DAO(dao::Call::dispatch_as_sub_account {
+				dao_id: 0u64,
+				index: 1u32,
+				call: Box::new(remark)
+			}))
+		));
+		let sub_account = dao::Pallet::<Test>::try_get_sub_account(0u64, 1u32).unwrap();
+		System::assert_has_event(Event::System(frame_system::Event::Remarked {
+			sender: sub_account,
+			hash: BlakeTwo256::hash(&[1; 10]),
+		}));
+	});
+}
+
+#[test]
+fn genesis_config_should_work() {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	crate::GenesisConfig::<Test> { accounts: vec![(0u64, ALICE)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		assert_eq!(crate::Account::<Test>::get(0u64), Some(ALICE));
+	});
+}
+
+#[test]
+pub fn call_list_should_work() {
+	new_test_ext().execute_with(|| {
+		set_sudo();
+		let remark = Call::DAO(dao::Call::dao_remark { dao_id: 0u64, remark: vec![1; 10] });
+		let set_listed = Call::DAO(dao::Call::set_call_listed {
+			dao_id: 0u64,
+			call_id: CallId::try_from(remark.clone()).unwrap(),
+			listed: true,
+		});
+		assert_ok!(crate::Pallet::<Test>::sudo(Origin::signed(ALICE), 0u64, Box::new(set_listed)));
+		assert_noop!(
+			crate::Pallet::<Test>::sudo(Origin::signed(ALICE), 0u64, Box::new(remark)),
+			dao::Error::<Test>::CallNotAllowed
+		);
+	});
+}